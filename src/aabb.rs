@@ -0,0 +1,124 @@
+use crate::primitives::{Point, Segment};
+use crate::scalar::Scalar;
+use crate::tolerance::{max_abs_coordinate, Tolerance};
+
+/// An axis-aligned bounding box.
+///
+/// A box whose `min` is above or to the right of its `max` holds no points; `Aabb::empty()`
+/// is the canonical one, and is what the bounds of an empty vertex list come out as. A box
+/// with `min == max` holds a single point and is not empty.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Aabb<T> {
+    pub min: Point<T>,
+    pub max: Point<T>,
+}
+
+impl<T: Scalar> Aabb<T> {
+    /// The box with opposite corners `a` and `b`, in either order.
+    pub fn new(a: Point<T>, b: Point<T>) -> Aabb<T> {
+        Aabb { min: Point::new(a.x.min(b.x), a.y.min(b.y)), max: Point::new(a.x.max(b.x), a.y.max(b.y)) }
+    }
+
+    /// The box holding no points, which is the identity for `union`.
+    pub fn empty() -> Aabb<T> {
+        Aabb { min: Point::new(T::MAX, T::MAX), max: Point::new(T::MIN, T::MIN) }
+    }
+
+    /// The smallest box holding every point in `points`.
+    pub fn from_points<'a>(points: impl IntoIterator<Item = &'a Point<T>>) -> Aabb<T> {
+        points.into_iter().fold(Aabb::empty(), |bounds, p| bounds.include(*p))
+    }
+
+    /// Converts the corners to another scalar type.
+    pub fn cast<U: Scalar>(self) -> Aabb<U> {
+        Aabb { min: self.min.cast(), max: self.max.cast() }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.min.x > self.max.x || self.min.y > self.max.y
+    }
+
+    pub fn width(&self) -> T {
+        self.max.x - self.min.x
+    }
+
+    pub fn height(&self) -> T {
+        self.max.y - self.min.y
+    }
+
+    pub fn center(&self) -> Point<T> {
+        Point::new((self.min.x + self.max.x) / T::TWO, (self.min.y + self.max.y) / T::TWO)
+    }
+
+    /// The smallest box holding both boxes.
+    pub fn union(&self, other: &Aabb<T>) -> Aabb<T> {
+        Aabb {
+            min: Point::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y)),
+            max: Point::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y)),
+        }
+    }
+
+    /// The region inside both boxes, which is empty if they do not overlap.
+    pub fn intersection(&self, other: &Aabb<T>) -> Aabb<T> {
+        let min = Point::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y));
+        let max = Point::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y));
+        if min.x > max.x || min.y > max.y {
+            Aabb::empty()
+        } else {
+            Aabb { min, max }
+        }
+    }
+
+    /// Whether the boxes share any point, including boxes that only touch.
+    pub fn intersects(&self, other: &Aabb<T>) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    /// Whether `p` is inside the box or on its boundary.
+    pub fn contains_point(&self, p: Point<T>) -> bool {
+        p.x >= self.min.x && p.x <= self.max.x && p.y >= self.min.y && p.y <= self.max.y
+    }
+
+    /// Whether every point of `other` is inside this box.
+    pub fn contains(&self, other: &Aabb<T>) -> bool {
+        other.is_empty() || (self.contains_point(other.min) && self.contains_point(other.max))
+    }
+
+    /// The box grown by `margin` on every side, or shrunk if `margin` is negative.
+    pub fn expand(&self, margin: T) -> Aabb<T> {
+        if self.is_empty() {
+            return *self;
+        }
+        Aabb {
+            min: Point::new(self.min.x - margin, self.min.y - margin),
+            max: Point::new(self.max.x + margin, self.max.y + margin),
+        }
+    }
+
+    /// The smallest box holding this one and `p`.
+    pub fn include(&self, p: Point<T>) -> Aabb<T> {
+        self.union(&Aabb { min: p, max: p })
+    }
+}
+
+impl From<Aabb<f32>> for Aabb<f64> {
+    fn from(aabb: Aabb<f32>) -> Aabb<f64> {
+        aabb.cast()
+    }
+}
+
+impl<T: Scalar> Segment<T> {
+    pub fn bounds(&self) -> Aabb<T> {
+        Aabb::new(self.p1, self.p2)
+    }
+}
+
+/// Whether the boxes come within `tolerance` of each other, so that shapes inside them might
+/// touch. Used to skip exact tests when they cannot.
+pub(crate) fn bounds_may_touch<T: Scalar>(a: &Aabb<T>, b: &Aabb<T>, tolerance: &Tolerance<T>) -> bool {
+    if a.is_empty() || b.is_empty() {
+        return false;
+    }
+    let eps = tolerance.epsilon_at(max_abs_coordinate(&[a.min, a.max, b.min, b.max]));
+    a.expand(eps).intersects(b)
+}
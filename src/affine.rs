@@ -0,0 +1,144 @@
+//! Affine transformations of the plane.
+
+use crate::convex::ConvexPolygon;
+use crate::primitives::{Line, Point, Segment};
+use crate::scalar::Scalar;
+
+/// The map `(x, y) → (a * x + b * y + tx, c * x + d * y + ty)`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Affine<T> {
+    pub a: T,
+    pub b: T,
+    pub c: T,
+    pub d: T,
+    pub tx: T,
+    pub ty: T,
+}
+
+impl<T: Scalar> Affine<T> {
+    pub fn new(a: T, b: T, c: T, d: T, tx: T, ty: T) -> Affine<T> {
+        Affine { a, b, c, d, tx, ty }
+    }
+
+    pub fn identity() -> Affine<T> {
+        Affine::new(T::ONE, T::ZERO, T::ZERO, T::ONE, T::ZERO, T::ZERO)
+    }
+
+    pub fn translation(dx: T, dy: T) -> Affine<T> {
+        Affine::new(T::ONE, T::ZERO, T::ZERO, T::ONE, dx, dy)
+    }
+
+    /// A counter-clockwise rotation by `angle` radians about the origin.
+    pub fn rotation(angle: T) -> Affine<T> {
+        let (sin, cos) = (angle.sin(), angle.cos());
+        Affine::new(cos, -sin, sin, cos, T::ZERO, T::ZERO)
+    }
+
+    /// A counter-clockwise rotation by `angle` radians about `center`.
+    pub fn rotation_about(center: Point<T>, angle: T) -> Affine<T> {
+        Affine::translation(-center.x, -center.y)
+            .then(&Affine::rotation(angle))
+            .then(&Affine::translation(center.x, center.y))
+    }
+
+    /// Scaling by `sx` along x and `sy` along y, about the origin.
+    pub fn scaling(sx: T, sy: T) -> Affine<T> {
+        Affine::new(sx, T::ZERO, T::ZERO, sy, T::ZERO, T::ZERO)
+    }
+
+    /// The shear `(x, y) → (x + kx * y, y + ky * x)`.
+    pub fn shear(kx: T, ky: T) -> Affine<T> {
+        Affine::new(T::ONE, kx, ky, T::ONE, T::ZERO, T::ZERO)
+    }
+
+    /// The mirror image across `line`. Coefficients with `a` and `b` both zero give the identity.
+    pub fn reflection(line: &Line<T>) -> Affine<T> {
+        let length_squared = line.a * line.a + line.b * line.b;
+        if length_squared == T::ZERO {
+            return Affine::identity();
+        }
+        // p → p - 2 (a x + b y - c) / (a² + b²) * (a, b)
+        let (ka, kb) = (T::TWO * line.a / length_squared, T::TWO * line.b / length_squared);
+        Affine::new(T::ONE - ka * line.a, -ka * line.b, -kb * line.a, T::ONE - kb * line.b, ka * line.c, kb * line.c)
+    }
+
+    /// Converts the coefficients to another scalar type.
+    pub fn cast<U: Scalar>(self) -> Affine<U> {
+        Affine::new(self.a.cast(), self.b.cast(), self.c.cast(), self.d.cast(), self.tx.cast(), self.ty.cast())
+    }
+
+    /// The transform that applies this one and then `next`.
+    pub fn then(&self, next: &Affine<T>) -> Affine<T> {
+        Affine::new(
+            next.a * self.a + next.b * self.c,
+            next.a * self.b + next.b * self.d,
+            next.c * self.a + next.d * self.c,
+            next.c * self.b + next.d * self.d,
+            next.a * self.tx + next.b * self.ty + next.tx,
+            next.c * self.tx + next.d * self.ty + next.ty,
+        )
+    }
+
+    /// The factor by which areas are scaled, negative when the transform mirrors.
+    pub fn determinant(&self) -> T {
+        self.a * self.d - self.b * self.c
+    }
+
+    /// The transform that undoes this one, or `None` if it flattens the plane.
+    pub fn inverse(&self) -> Option<Affine<T>> {
+        let det = self.determinant();
+        if det == T::ZERO {
+            return None;
+        }
+        let (a, b, c, d) = (self.d / det, -self.b / det, -self.c / det, self.a / det);
+        Some(Affine::new(a, b, c, d, -(a * self.tx + b * self.ty), -(c * self.tx + d * self.ty)))
+    }
+
+    pub fn apply(&self, p: Point<T>) -> Point<T> {
+        Point::new(self.a * p.x + self.b * p.y + self.tx, self.c * p.x + self.d * p.y + self.ty)
+    }
+}
+
+impl From<Affine<f32>> for Affine<f64> {
+    fn from(affine: Affine<f32>) -> Affine<f64> {
+        affine.cast()
+    }
+}
+
+impl<T: Scalar> Point<T> {
+    pub fn transform(&self, affine: &Affine<T>) -> Point<T> {
+        affine.apply(*self)
+    }
+}
+
+impl<T: Scalar> Segment<T> {
+    pub fn transform(&self, affine: &Affine<T>) -> Segment<T> {
+        Segment::new(affine.apply(self.p1), affine.apply(self.p2))
+    }
+}
+
+impl<T: Scalar> Line<T> {
+    /// The image of the line, keeping its direction. A transform that flattens the line to a
+    /// point gives `a` and `b` both zero.
+    pub fn transform(&self, affine: &Affine<T>) -> Line<T> {
+        let p = self.closest_point(Point::new(T::ZERO, T::ZERO));
+        let direction = self.direction();
+        let q = Point::new(p.x + direction.x, p.y + direction.y);
+        Line::from_points(affine.apply(p), affine.apply(q))
+    }
+}
+
+impl<T: Scalar> ConvexPolygon<T> {
+    /// The image of the polygon, which is still convex. The vertices are reversed after a
+    /// mirroring transform so that they keep running the same way.
+    ///
+    /// A transform that flattens the plane flattens the polygon too, which is not checked for,
+    /// as with `cast`.
+    pub fn transform(&self, affine: &Affine<T>) -> ConvexPolygon<T> {
+        let mut vertices: Vec<Point<T>> = self.vertices.iter().map(|p| affine.apply(*p)).collect();
+        if affine.determinant() < T::ZERO {
+            vertices.reverse();
+        }
+        ConvexPolygon { vertices }
+    }
+}
@@ -0,0 +1,371 @@
+//! Boolean operations on simple polygons.
+//!
+//! Both boundaries are split wherever they meet, so every piece of either
+//! boundary lies wholly inside the other polygon, outside it, or along one of
+//! its edges. Each operation keeps a different selection of those pieces and
+//! joins them back up into rings.
+
+use crate::multipolygon::{MultiPolygon, PolygonWithHoles};
+use crate::polygon::{leftmost_lowest, ring_contains, ring_twice_area, Polygon};
+use crate::primitives::{intersect_line_segments_with_tolerance, is_left, near_segment, Point, Segment};
+use crate::scalar::Scalar;
+use crate::tolerance::Tolerance;
+
+/// Which boolean operation to apply to two polygons.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BooleanOp {
+    /// The region inside both polygons.
+    Intersection,
+    /// The region inside either polygon.
+    Union,
+    /// The region inside the first polygon but not the second.
+    Difference,
+    /// The region inside exactly one of the polygons.
+    Xor,
+}
+
+/// Where a piece of one boundary lies relative to the other polygon.
+#[derive(PartialEq, Clone, Copy)]
+enum Location {
+    Inside,
+    Outside,
+    /// Along an edge of the other polygon running the same way.
+    SameEdge,
+    /// Along an edge of the other polygon running the opposite way.
+    OppositeEdge,
+}
+
+/// A piece of boundary between two nodes, as indices into the node list.
+type Piece = (usize, usize);
+
+/// For each edge of a ring, the points where it must be cut.
+type EdgeSplits<T> = Vec<Vec<Point<T>>>;
+
+/// Rings turned counter-clockwise, the nodes they were split at, and each ring's pieces.
+type SplitRings<T> = (Vec<Vec<Point<T>>>, Vec<Point<T>>, Vec<Vec<Piece>>);
+
+impl<T: Scalar> Polygon<T> {
+    /// The region inside both polygons.
+    pub fn intersection(&self, other: &Polygon<T>) -> MultiPolygon<T> {
+        self.boolean_with_tolerance(other, BooleanOp::Intersection, &Tolerance::default())
+    }
+
+    /// The region inside either polygon.
+    pub fn union(&self, other: &Polygon<T>) -> MultiPolygon<T> {
+        self.boolean_with_tolerance(other, BooleanOp::Union, &Tolerance::default())
+    }
+
+    /// The region inside this polygon but not `other`.
+    pub fn difference(&self, other: &Polygon<T>) -> MultiPolygon<T> {
+        self.boolean_with_tolerance(other, BooleanOp::Difference, &Tolerance::default())
+    }
+
+    /// The region inside exactly one of the polygons.
+    pub fn xor(&self, other: &Polygon<T>) -> MultiPolygon<T> {
+        self.boolean_with_tolerance(other, BooleanOp::Xor, &Tolerance::default())
+    }
+
+    /// Applies `op`, treating points within `tolerance` of each other as the same point.
+    ///
+    /// Result rings start at their leftmost-lowest vertex and have no collinear vertices.
+    /// Every edge of one polygon is tested against every edge of the other, so this takes
+    /// O(nm) time.
+    pub fn boolean_with_tolerance(&self, other: &Polygon<T>, op: BooleanOp, tolerance: &Tolerance<T>) -> MultiPolygon<T> {
+        let a = counter_clockwise(&self.vertices);
+        let b = counter_clockwise(&other.vertices);
+        let (splits_a, splits_b) = split_points(&a, &b, tolerance);
+
+        let mut nodes: Vec<Point<T>> = Vec::new();
+        let pieces_a = split_ring(&a, &splits_a, &mut nodes, tolerance);
+        let pieces_b = split_ring(&b, &splits_b, &mut nodes, tolerance);
+
+        let mut kept: Vec<Piece> = Vec::new();
+        for (pieces, others, ring, from_a) in [(&pieces_a, &pieces_b, &b, true), (&pieces_b, &pieces_a, &a, false)] {
+            for &(start, end) in pieces {
+                let location = locate(start, end, others, &nodes, ring);
+                match select(op, location, from_a) {
+                    Some(false) => kept.push((start, end)),
+                    Some(true) => kept.push((end, start)),
+                    None => {}
+                }
+            }
+        }
+        assemble(stitch(&nodes, &kept))
+    }
+}
+
+/// Splits every ring wherever another meets it, returning the shared nodes and each ring's
+/// pieces. The rings are turned counter-clockwise first.
+fn split_rings<T: Scalar>(rings: &[Vec<Point<T>>], tolerance: &Tolerance<T>) -> SplitRings<T> {
+    let rings: Vec<Vec<Point<T>>> = rings.iter().map(|ring| counter_clockwise(ring)).collect();
+    let mut splits: Vec<EdgeSplits<T>> = rings.iter().map(|ring| vec![Vec::new(); ring.len()]).collect();
+    for i in 0..rings.len() {
+        for j in i + 1..rings.len() {
+            let (splits_i, splits_j) = split_points(&rings[i], &rings[j], tolerance);
+            for (all, found) in [(i, splits_i), (j, splits_j)] {
+                for (edge, points) in found.into_iter().enumerate() {
+                    splits[all][edge].extend(points);
+                }
+            }
+        }
+    }
+    let mut nodes: Vec<Point<T>> = Vec::new();
+    let pieces = rings.iter().zip(&splits).map(|(ring, splits)| split_ring(ring, splits, &mut nodes, tolerance)).collect();
+    (rings, nodes, pieces)
+}
+
+/// Whether piece `k` of ring `ring` belongs to the boundary of the union of `rings[among]`:
+/// it must be outside every other ring of those, and of pieces shared along an edge only
+/// the first ring's copy is kept.
+fn on_union_boundary<T: Scalar>(piece: Piece, ring: usize, among: std::ops::Range<usize>, rings: &[Vec<Point<T>>], nodes: &[Point<T>], pieces: &[Vec<Piece>]) -> bool {
+    among.filter(|&j| j != ring).all(|j| match locate(piece.0, piece.1, &pieces[j], nodes, &rings[j]) {
+        Location::Outside => true,
+        Location::SameEdge => j > ring,
+        Location::Inside | Location::OppositeEdge => false,
+    })
+}
+
+/// The region inside any of the rings, which may overlap and run either way.
+///
+/// Every ring is tested against every other, so this takes time quadratic in the total
+/// number of edges.
+pub(crate) fn union_rings<T: Scalar>(rings: &[Vec<Point<T>>], tolerance: &Tolerance<T>) -> MultiPolygon<T> {
+    let (rings, nodes, pieces) = split_rings(rings, tolerance);
+    let mut kept: Vec<Piece> = Vec::new();
+    for (i, ring_pieces) in pieces.iter().enumerate() {
+        kept.extend(ring_pieces.iter().filter(|&&piece| on_union_boundary(piece, i, 0..rings.len(), &rings, &nodes, &pieces)));
+    }
+    assemble(stitch(&nodes, &kept))
+}
+
+/// The region inside `base` but outside all of the `cutters`, which may overlap and run
+/// either way.
+pub(crate) fn subtract_rings<T: Scalar>(base: &[Point<T>], cutters: &[Vec<Point<T>>], tolerance: &Tolerance<T>) -> MultiPolygon<T> {
+    let all: Vec<Vec<Point<T>>> = std::iter::once(base.to_vec()).chain(cutters.iter().cloned()).collect();
+    let (rings, nodes, pieces) = split_rings(&all, tolerance);
+    let cutters = 1..rings.len();
+    let mut kept: Vec<Piece> = Vec::new();
+    // The base boundary where no cutter covers the inside next to it
+    for &piece in &pieces[0] {
+        let kept_here = cutters.clone().all(|j| {
+            matches!(locate(piece.0, piece.1, &pieces[j], &nodes, &rings[j]), Location::Outside | Location::OppositeEdge)
+        });
+        if kept_here {
+            kept.push(piece);
+        }
+    }
+    // The boundary of the cutters' union inside the base, reversed to face the region left
+    for i in cutters.clone() {
+        for &piece in &pieces[i] {
+            if locate(piece.0, piece.1, &pieces[0], &nodes, &rings[0]) == Location::Inside
+                && on_union_boundary(piece, i, cutters.clone(), &rings, &nodes, &pieces)
+            {
+                kept.push((piece.1, piece.0));
+            }
+        }
+    }
+    assemble(stitch(&nodes, &kept))
+}
+
+/// Whether a piece at `location` belongs to the result of `op`, and if so whether it must be
+/// reversed. Pieces along shared edges are taken from the first polygon only.
+fn select(op: BooleanOp, location: Location, from_a: bool) -> Option<bool> {
+    match (op, location) {
+        (BooleanOp::Intersection, Location::Inside) => Some(false),
+        (BooleanOp::Intersection, Location::SameEdge) if from_a => Some(false),
+        (BooleanOp::Union, Location::Outside) => Some(false),
+        (BooleanOp::Union, Location::SameEdge) if from_a => Some(false),
+        (BooleanOp::Difference, Location::Outside) if from_a => Some(false),
+        (BooleanOp::Difference, Location::OppositeEdge) if from_a => Some(false),
+        (BooleanOp::Difference, Location::Inside) if !from_a => Some(true),
+        (BooleanOp::Xor, Location::Outside) => Some(false),
+        (BooleanOp::Xor, Location::Inside) => Some(true),
+        _ => None,
+    }
+}
+
+/// `1.0` if the ring `vertices` runs counter-clockwise and `-1.0` if it runs clockwise.
+/// Outside is to the right of each edge for the first and to the left for the second, so
+/// multiplying an `is_left` sign by this makes it positive inside whichever way the ring runs.
+pub(crate) fn orientation_sign<T: Scalar>(vertices: &[Point<T>]) -> f64 {
+    if ring_twice_area(vertices) < T::ZERO { -1.0 } else { 1.0 }
+}
+
+/// The ring `vertices`, reversed if it runs clockwise.
+pub(crate) fn counter_clockwise<T: Scalar>(vertices: &[Point<T>]) -> Vec<Point<T>> {
+    let mut ring = vertices.to_vec();
+    if ring_twice_area(&ring) < T::ZERO {
+        ring.reverse();
+    }
+    ring
+}
+
+/// For every edge of each ring, the points where the other ring meets it.
+fn split_points<T: Scalar>(a: &[Point<T>], b: &[Point<T>], tolerance: &Tolerance<T>) -> (EdgeSplits<T>, EdgeSplits<T>) {
+    let (n, m) = (a.len(), b.len());
+    let mut splits_a = vec![Vec::new(); n];
+    let mut splits_b = vec![Vec::new(); m];
+    for i in 0..n {
+        let edge_a = Segment::new(a[i], a[(i + 1) % n]);
+        for j in 0..m {
+            let edge_b = Segment::new(b[j], b[(j + 1) % m]);
+            // Vertices on the other edge cover touching and overlapping edges; only
+            // if there are none can the edges cross at a new point.
+            let mut touching = false;
+            for p in [edge_b.p1, edge_b.p2] {
+                if near_segment(p, &edge_a, tolerance) {
+                    splits_a[i].push(p);
+                    touching = true;
+                }
+            }
+            for p in [edge_a.p1, edge_a.p2] {
+                if near_segment(p, &edge_b, tolerance) {
+                    splits_b[j].push(p);
+                    touching = true;
+                }
+            }
+            if !touching {
+                if let Some(p) = intersect_line_segments_with_tolerance(&edge_a, &edge_b, tolerance) {
+                    splits_a[i].push(p);
+                    splits_b[j].push(p);
+                }
+            }
+        }
+    }
+    (splits_a, splits_b)
+}
+
+/// The index of the node equal to `p` within `tolerance`, adding it if there is none.
+fn node_index<T: Scalar>(nodes: &mut Vec<Point<T>>, p: Point<T>, tolerance: &Tolerance<T>) -> usize {
+    match nodes.iter().position(|q| tolerance.points_eq(*q, p)) {
+        Some(index) => index,
+        None => {
+            nodes.push(p);
+            nodes.len() - 1
+        }
+    }
+}
+
+/// Cuts each edge of `ring` at its split points, in order along the edge.
+fn split_ring<T: Scalar>(ring: &[Point<T>], splits: &[Vec<Point<T>>], nodes: &mut Vec<Point<T>>, tolerance: &Tolerance<T>) -> Vec<Piece> {
+    let n = ring.len();
+    let mut pieces = Vec::new();
+    for i in 0..n {
+        let (start, end) = (ring[i], ring[(i + 1) % n]);
+        let mut points = splits[i].clone();
+        points.sort_by(|p, q| start.distance(*p).partial_cmp(&start.distance(*q)).unwrap());
+        let mut previous = node_index(nodes, start, tolerance);
+        for p in points.into_iter().chain([end]) {
+            let index = node_index(nodes, p, tolerance);
+            if index != previous {
+                pieces.push((previous, index));
+                previous = index;
+            }
+        }
+    }
+    pieces
+}
+
+fn locate<T: Scalar>(start: usize, end: usize, others: &[Piece], nodes: &[Point<T>], ring: &[Point<T>]) -> Location {
+    if others.contains(&(start, end)) {
+        return Location::SameEdge;
+    }
+    if others.contains(&(end, start)) {
+        return Location::OppositeEdge;
+    }
+    let (p, q) = (nodes[start], nodes[end]);
+    let midpoint = Point::new((p.x + q.x) / T::TWO, (p.y + q.y) / T::TWO);
+    if ring_contains(ring, midpoint, &Tolerance::exact()) {
+        Location::Inside
+    } else {
+        Location::Outside
+    }
+}
+
+/// Clockwise angle from direction `from` round to direction `to`, in (0, 2π].
+fn clockwise_angle<T: Scalar>(from: Point<T>, to: Point<T>) -> T {
+    let angle = from.y.atan2(from.x) - to.y.atan2(to.x);
+    if angle <= T::ZERO {
+        angle + T::TWO * T::PI
+    } else if angle > T::TWO * T::PI {
+        angle - T::TWO * T::PI
+    } else {
+        angle
+    }
+}
+
+/// Joins pieces end to end into closed rings. Where several pieces leave the same node the
+/// sharpest left turn is taken, so rings that only touch at a vertex come out separately.
+fn stitch<T: Scalar>(nodes: &[Point<T>], pieces: &[Piece]) -> Vec<Vec<Point<T>>> {
+    let mut used = vec![false; pieces.len()];
+    let mut rings = Vec::new();
+    for first in 0..pieces.len() {
+        if used[first] {
+            continue;
+        }
+        used[first] = true;
+        let mut ring = vec![nodes[pieces[first].0]];
+        let mut current = first;
+        let closed = loop {
+            let (from, to) = pieces[current];
+            if to == pieces[first].0 {
+                break true;
+            }
+            ring.push(nodes[to]);
+            let back = Point::new(nodes[from].x - nodes[to].x, nodes[from].y - nodes[to].y);
+            let next = (0..pieces.len()).filter(|&k| !used[k] && pieces[k].0 == to).min_by(|&j, &k| {
+                let turn = |piece: Piece| clockwise_angle(back, Point::new(nodes[piece.1].x - nodes[to].x, nodes[piece.1].y - nodes[to].y));
+                turn(pieces[j]).partial_cmp(&turn(pieces[k])).unwrap()
+            });
+            match next {
+                Some(k) => {
+                    used[k] = true;
+                    current = k;
+                }
+                None => break false,
+            }
+        };
+        if closed {
+            rings.push(ring);
+        }
+    }
+    rings
+}
+
+/// Removes vertices in the middle of straight runs and starts the ring at its leftmost-lowest vertex.
+fn tidy_ring<T: Scalar>(mut ring: Vec<Point<T>>) -> Vec<Point<T>> {
+    let mut i = 0;
+    while ring.len() >= 3 && i < ring.len() {
+        let n = ring.len();
+        if is_left(ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]) == 0.0 {
+            ring.remove(i);
+            i = i.saturating_sub(1);
+        } else {
+            i += 1;
+        }
+    }
+    let start = leftmost_lowest(&ring);
+    ring.rotate_left(start);
+    ring
+}
+
+/// Sorts rings into counter-clockwise exteriors and clockwise holes, putting each hole in the
+/// smallest exterior that contains it.
+fn assemble<T: Scalar>(rings: Vec<Vec<Point<T>>>) -> MultiPolygon<T> {
+    let rings: Vec<Vec<Point<T>>> = rings.into_iter().map(tidy_ring).filter(|r| r.len() >= 3).collect();
+    let (exteriors, holes): (Vec<_>, Vec<_>) = rings.into_iter().partition(|r| ring_twice_area(r) > T::ZERO);
+
+    let mut polygons: Vec<PolygonWithHoles<T>> = exteriors
+        .into_iter()
+        .map(|vertices| PolygonWithHoles { exterior: Polygon { vertices }, interiors: Vec::new() })
+        .collect();
+    for hole in holes {
+        let owner = (0..polygons.len())
+            .filter(|&i| hole.iter().all(|v| ring_contains(&polygons[i].exterior.vertices, *v, &Tolerance::default())))
+            .min_by(|&i, &j| polygons[i].exterior.area().partial_cmp(&polygons[j].exterior.area()).unwrap());
+        if let Some(i) = owner {
+            polygons[i].interiors.push(Polygon { vertices: hole });
+        }
+    }
+    MultiPolygon { polygons }
+}
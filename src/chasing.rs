@@ -0,0 +1,175 @@
+//! Linear-time intersection of two convex polygons by "chasing" their edges
+//! around each other, after O'Rourke, Chien, Olson and Naddor, "A new linear
+//! algorithm for intersecting convex polygons" (1982), as presented in
+//! O'Rourke's *Computational Geometry in C*, section 7.6.
+
+use crate::convex::{ConvexIntersection, ConvexPolygon};
+use crate::polygon::{dedup_consecutive, ring_twice_area};
+use crate::predicates::cross_sign;
+use crate::primitives::{intersect_line_segments_with_tolerance, is_left, Point, Segment};
+use crate::scalar::Scalar;
+use crate::tolerance::Tolerance;
+
+/// Which polygon's boundary is currently the inner one.
+#[derive(PartialEq, Clone, Copy)]
+enum InFlag {
+    Unknown,
+    P,
+    Q,
+}
+
+/// Intersects two convex polygons whose vertices are in counter-clockwise
+/// order in O(n + m), emitting the result boundary in order as it is found.
+pub(crate) fn intersect_chasing<T: Scalar>(poly_p: &ConvexPolygon<T>, poly_q: &ConvexPolygon<T>, tolerance: &Tolerance<T>) -> ConvexIntersection<T> {
+    let (p, q) = (&poly_p.vertices, &poly_q.vertices);
+    let (n, m) = (p.len(), q.len());
+    if n == 0 || m == 0 {
+        return ConvexIntersection::Empty;
+    }
+
+    let mut out: Vec<Point<T>> = Vec::new();
+    let (mut a, mut b) = (0, 0);
+    // how many times each polygon has been advanced
+    let (mut aa, mut ba) = (0, 0);
+    let mut inflag = InFlag::Unknown;
+    let mut first_point = true;
+
+    loop {
+        let a1 = (a + n - 1) % n;
+        let b1 = (b + m - 1) % m;
+        let edge_p = Segment::new(p[a1], p[a]);
+        let edge_q = Segment::new(q[b1], q[b]);
+
+        let cross = cross_sign(p[a1], p[a], q[b1], q[b]);
+        // which side of the other polygon's current edge each edge head is on
+        let a_hb = is_left(q[b1], q[b], p[a]);
+        let b_ha = is_left(p[a1], p[a], q[b]);
+        let collinear = is_left(q[b1], q[b], p[a1]) == 0.0 && a_hb == 0.0;
+
+        if collinear {
+            let (dx_p, dy_p) = (p[a].x - p[a1].x, p[a].y - p[a1].y);
+            let (dx_q, dy_q) = (q[b].x - q[b1].x, q[b].y - q[b1].y);
+            if dx_p * dx_q + dy_p * dy_q < T::ZERO {
+                // Edges run opposite ways along one line, so the polygons lie on either
+                // side of it and can only share (part of) this edge.
+                return shared_edge(&edge_p, &edge_q);
+            }
+        } else if let Some(x) = intersect_line_segments_with_tolerance(&edge_p, &edge_q, &Tolerance::exact()) {
+            if inflag == InFlag::Unknown && first_point {
+                aa = 0;
+                ba = 0;
+                first_point = false;
+            }
+            out.push(x);
+            if a_hb > 0.0 {
+                inflag = InFlag::P;
+            } else if b_ha > 0.0 {
+                inflag = InFlag::Q;
+            }
+        }
+
+        if cross == 0.0 && a_hb < 0.0 && b_ha < 0.0 {
+            // Parallel edges facing away from each other: the polygons are disjoint
+            return ConvexIntersection::Empty;
+        }
+
+        let advance_p = if cross == 0.0 && a_hb == 0.0 && b_ha == 0.0 {
+            inflag != InFlag::P
+        } else if cross >= 0.0 {
+            b_ha > 0.0
+        } else {
+            a_hb <= 0.0
+        };
+        if advance_p {
+            if inflag == InFlag::P {
+                out.push(p[a]);
+            }
+            aa += 1;
+            a = (a + 1) % n;
+        } else {
+            if inflag == InFlag::Q {
+                out.push(q[b]);
+            }
+            ba += 1;
+            b = (b + 1) % m;
+        }
+
+        if !((aa < n || ba < m) && aa < 2 * n && ba < 2 * m) {
+            break;
+        }
+    }
+
+    if inflag == InFlag::Unknown && (strictly_inside_convex(q, vertex_average(p)) || strictly_inside_convex(p, vertex_average(q))) {
+        // The boundaries never cross but the interiors overlap, so the smaller polygon is inside the larger.
+        return if ring_twice_area(p) <= ring_twice_area(q) {
+            ConvexIntersection::Polygon(poly_p.clone())
+        } else {
+            ConvexIntersection::Polygon(poly_q.clone())
+        };
+    }
+    ordered_intersection(out, tolerance)
+}
+
+/// Whether `p` is strictly left of every edge of the counter-clockwise ring `vertices`,
+/// decided exactly.
+fn strictly_inside_convex<T: Scalar>(vertices: &[Point<T>], p: Point<T>) -> bool {
+    let n = vertices.len();
+    (0..n).all(|i| is_left(vertices[i], vertices[(i + 1) % n], p) > 0.0)
+}
+
+/// The overlap of two collinear edges that run in opposite directions.
+fn shared_edge<T: Scalar>(edge_p: &Segment<T>, edge_q: &Segment<T>) -> ConvexIntersection<T> {
+    let (origin, (dx, dy)) = (edge_p.p1, (edge_p.p2.x - edge_p.p1.x, edge_p.p2.y - edge_p.p1.y));
+    let t = |v: Point<T>| (v.x - origin.x) * dx + (v.y - origin.y) * dy;
+    let (p_lo, p_hi) = (edge_p.p1, edge_p.p2);
+    let (q_lo, q_hi) = if t(edge_q.p1) <= t(edge_q.p2) { (edge_q.p1, edge_q.p2) } else { (edge_q.p2, edge_q.p1) };
+    let lo = if t(q_lo) > t(p_lo) { q_lo } else { p_lo };
+    let hi = if t(q_hi) < t(p_hi) { q_hi } else { p_hi };
+    if t(lo) > t(hi) {
+        ConvexIntersection::Empty
+    } else if lo == hi {
+        ConvexIntersection::Point(lo)
+    } else {
+        ConvexIntersection::Segment(Segment::new(lo, hi))
+    }
+}
+
+/// The vertex average, which is strictly inside any convex polygon with area.
+fn vertex_average<T: Scalar>(vertices: &[Point<T>]) -> Point<T> {
+    let n = T::from_usize(vertices.len());
+    Point::new(vertices.iter().map(|v| v.x).sum::<T>() / n, vertices.iter().map(|v| v.y).sum::<T>() / n)
+}
+
+/// Cleans up the boundary emitted by the chase: merges repeated points, drops
+/// points in the middle of straight runs and classifies what is left.
+fn ordered_intersection<T: Scalar>(out: Vec<Point<T>>, tolerance: &Tolerance<T>) -> ConvexIntersection<T> {
+    let ring = dedup_consecutive(&out, tolerance);
+
+    match ring.len() {
+        0 => return ConvexIntersection::Empty,
+        1 => return ConvexIntersection::Point(ring[0]),
+        _ => {}
+    }
+
+    let n = ring.len();
+    let corners: Vec<Point<T>> = (0..n)
+        .filter(|&i| is_left(ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]) != 0.0)
+        .map(|i| ring[i])
+        .collect();
+    if corners.len() < 3 {
+        // Every point is on one line; the chase visits it out and back.
+        let farthest_from = |p: Point<T>| ring.iter().copied().fold(p, |best, v| if p.distance(v) > p.distance(best) { v } else { best });
+        let a = farthest_from(ring[0]);
+        return ConvexIntersection::Segment(Segment::new(a, farthest_from(a)));
+    }
+
+    // Start at the vertex `ConvexPolygon::new` would put first, so both agree exactly.
+    let centre = vertex_average(&corners);
+    let angle = |v: &Point<T>| (v.y - centre.y).atan2(v.x - centre.x);
+    let start = (0..corners.len())
+        .min_by(|&i, &j| angle(&corners[i]).partial_cmp(&angle(&corners[j])).unwrap())
+        .unwrap_or(0);
+    let mut vertices = corners;
+    vertices.rotate_left(start);
+    ConvexIntersection::Polygon(ConvexPolygon { vertices })
+}
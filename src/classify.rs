@@ -0,0 +1,134 @@
+//! Where a point lies relative to a polygon, telling the boundary apart from the
+//! inside.
+
+use crate::convex::ConvexPolygon;
+use crate::polygon::{winding_number, Polygon};
+use crate::primitives::{is_left, near_segment, Point, Segment};
+use crate::scalar::Scalar;
+use crate::tolerance::Tolerance;
+
+/// Where a point lies relative to a polygon.
+///
+/// Edge `i` runs from vertex `i` to vertex `i + 1`, wrapping around to vertex 0.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PointLocation {
+    Inside,
+    Outside,
+    /// On edge `i`, away from its end points.
+    OnEdge(usize),
+    /// On vertex `i`.
+    OnVertex(usize),
+}
+
+impl PointLocation {
+    /// Whether the point is on a vertex or an edge.
+    pub fn is_on_boundary(&self) -> bool {
+        matches!(self, PointLocation::OnEdge(_) | PointLocation::OnVertex(_))
+    }
+}
+
+/// The first vertex at either end of the `edges` that `p` is within `tolerance` of, or
+/// failing that the first of the `edges` that `p` lies exactly on.
+fn on_boundary<T: Scalar>(vertices: &[Point<T>], edges: &[usize], p: Point<T>, tolerance: &Tolerance<T>) -> Option<PointLocation> {
+    let n = vertices.len();
+    let mut ends = edges.iter().flat_map(|&i| [i, (i + 1) % n]);
+    if let Some(i) = ends.find(|&i| tolerance.points_eq(vertices[i], p)) {
+        return Some(PointLocation::OnVertex(i));
+    }
+    edges
+        .iter()
+        .copied()
+        .find(|&i| near_segment(p, &Segment::new(vertices[i], vertices[(i + 1) % n]), &Tolerance::exact()))
+        .map(PointLocation::OnEdge)
+}
+
+/// Classifies `p` against the ring `vertices`, which may run either way.
+fn classify_in_ring<T: Scalar>(vertices: &[Point<T>], p: Point<T>, tolerance: &Tolerance<T>) -> PointLocation {
+    let edges: Vec<usize> = (0..vertices.len()).collect();
+    if let Some(location) = on_boundary(vertices, &edges, p, tolerance) {
+        location
+    } else if winding_number(vertices, p) != 0 {
+        PointLocation::Inside
+    } else {
+        PointLocation::Outside
+    }
+}
+
+impl<T: Scalar> ConvexPolygon<T> {
+    /// Where `p` lies, within `Tolerance::default()`. Unlike `is_point_inside` this tells
+    /// points on the boundary apart, and says which edge or vertex they are on.
+    ///
+    /// Runs in O(n); see `classify_point_binary_search` for large polygons.
+    pub fn classify_point(&self, p: Point<T>) -> PointLocation {
+        self.classify_point_with_tolerance(p, &Tolerance::default())
+    }
+
+    /// Like `classify_point`, treating points within `tolerance` of a vertex as on it.
+    /// Whether a point is on an edge, or which side of it, is decided exactly.
+    pub fn classify_point_with_tolerance(&self, p: Point<T>, tolerance: &Tolerance<T>) -> PointLocation {
+        classify_in_ring(&self.vertices, p, tolerance)
+    }
+
+    /// Like `classify_point`, but runs in O(log n) by finding which triangle of the fan from
+    /// vertex 0 holds `p`. Relies on the vertices running counter-clockwise, as `new` leaves them.
+    pub fn classify_point_binary_search(&self, p: Point<T>) -> PointLocation {
+        self.classify_point_binary_search_with_tolerance(p, &Tolerance::default())
+    }
+
+    /// Like `classify_point_binary_search`, comparing points within `tolerance`.
+    ///
+    /// Only the vertices around the triangle found are checked against `tolerance`, so this
+    /// can differ from `classify_point_with_tolerance` for polygons with edges shorter than it.
+    pub fn classify_point_binary_search_with_tolerance(&self, p: Point<T>, tolerance: &Tolerance<T>) -> PointLocation {
+        let vertices = &self.vertices;
+        let n = vertices.len();
+        if n < 3 {
+            return self.classify_point_with_tolerance(p, tolerance);
+        }
+        let v0 = vertices[0];
+
+        // The last fan triangle v0, v[k], v[k + 1] whose first side has `p` on its left
+        let in_wedge = is_left(v0, vertices[1], p) >= 0.0 && is_left(v0, vertices[n - 1], p) <= 0.0;
+        let k = if in_wedge {
+            let (mut lo, mut hi) = (1, n - 2);
+            while lo < hi {
+                let mid = (lo + hi).div_ceil(2);
+                if is_left(v0, vertices[mid], p) >= 0.0 {
+                    lo = mid;
+                } else {
+                    hi = mid - 1;
+                }
+            }
+            lo
+        } else {
+            1
+        };
+
+        // The edges `p` could be near: those of the triangle and the ones either side of it
+        let mut edges = vec![0, n - 1, k - 1, k, k + 1];
+        if !in_wedge {
+            edges.push(n - 2);
+        }
+        if let Some(location) = on_boundary(vertices, &edges, p, tolerance) {
+            location
+        } else if in_wedge && is_left(vertices[k], vertices[k + 1], p) > 0.0 {
+            PointLocation::Inside
+        } else {
+            PointLocation::Outside
+        }
+    }
+}
+
+impl<T: Scalar> Polygon<T> {
+    /// Where `p` lies, within `Tolerance::default()`. Unlike `is_point_inside` this tells
+    /// points on the boundary apart, and says which edge or vertex they are on.
+    pub fn classify_point(&self, p: Point<T>) -> PointLocation {
+        self.classify_point_with_tolerance(p, &Tolerance::default())
+    }
+
+    /// Like `classify_point`, treating points within `tolerance` of a vertex as on it.
+    /// Whether a point is on an edge, or which side of it, is decided exactly.
+    pub fn classify_point_with_tolerance(&self, p: Point<T>, tolerance: &Tolerance<T>) -> PointLocation {
+        classify_in_ring(&self.vertices, p, tolerance)
+    }
+}
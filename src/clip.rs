@@ -0,0 +1,179 @@
+//! Clipping to convex polygons: segments, rays and lines with the Cyrus–Beck
+//! algorithm, and whole rings with Sutherland–Hodgman.
+//!
+//! Cyrus–Beck writes the line as `origin + t * direction`. Each edge of the
+//! polygon bounds `t` from below, where the line crosses into its inner side,
+//! or from above, where it crosses out; what is left of the range once every
+//! edge has had its say is the part inside the polygon.
+//!
+//! Sutherland–Hodgman cuts the ring against the line through each edge of the
+//! window in turn, keeping the part on the inner side.
+
+use crate::boolean::orientation_sign;
+use crate::convex::ConvexPolygon;
+use crate::primitives::{intersect_lines, is_left, Line, Point, Segment};
+use crate::scalar::Scalar;
+
+/// The part of a segment, ray or line inside a convex polygon, as the range `t0..=t1` of
+/// the parameter `t` along `origin + t * direction`.
+///
+/// Edge `i` runs from vertex `i` to vertex `i + 1`, wrapping around to vertex 0.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Clip<T> {
+    pub t0: T,
+    pub t1: T,
+    /// The point at `t0`.
+    pub start: Point<T>,
+    /// The point at `t1`.
+    pub end: Point<T>,
+    /// The edge crossed at `t0`, or `None` if the range starts inside the polygon.
+    pub entry_edge: Option<usize>,
+    /// The edge crossed at `t1`, or `None` if the range ends inside the polygon.
+    pub exit_edge: Option<usize>,
+}
+
+impl<T: Scalar> Clip<T> {
+    /// The clipped part as a segment from `start` to `end`.
+    pub fn segment(&self) -> Segment<T> {
+        Segment::new(self.start, self.end)
+    }
+}
+
+impl<T: Scalar> ConvexPolygon<T> {
+    /// The part of `segment` inside the polygon or on its boundary, running the same way as
+    /// `segment`, or `None` if they do not meet. A segment that only touches the polygon gives
+    /// a zero-length segment.
+    pub fn clip_segment(&self, segment: &Segment<T>) -> Option<Segment<T>> {
+        self.clip_segment_parametric(segment).map(|clip| clip.segment())
+    }
+
+    /// Like `clip_segment`, with `t` running from 0 at `segment.p1` to 1 at `segment.p2`.
+    pub fn clip_segment_parametric(&self, segment: &Segment<T>) -> Option<Clip<T>> {
+        let direction = Point::new(segment.p2.x - segment.p1.x, segment.p2.y - segment.p1.y);
+        let clip = self.cyrus_beck(segment.p1, direction, T::ZERO, T::ONE)?;
+        // Land exactly on the ends of the segment where they are not clipped.
+        Some(Clip {
+            start: if clip.entry_edge.is_none() { segment.p1 } else { clip.start },
+            end: if clip.exit_edge.is_none() { segment.p2 } else { clip.end },
+            ..clip
+        })
+    }
+
+    /// The part of the ray from `origin` along `direction` inside the polygon, with `t` in
+    /// multiples of `direction`, or `None` if the ray misses it.
+    pub fn clip_ray(&self, origin: Point<T>, direction: Point<T>) -> Option<Clip<T>> {
+        self.cyrus_beck(origin, direction, T::ZERO, T::MAX)
+    }
+
+    /// The part of `line` inside the polygon, or `None` if the line misses it.
+    ///
+    /// `t` is measured from the point of the line nearest the coordinate origin, in multiples
+    /// of the direction `(-b, a)`.
+    pub fn clip_line(&self, line: &Line<T>) -> Option<Clip<T>> {
+        let length_squared = line.a * line.a + line.b * line.b;
+        if length_squared == T::ZERO {
+            return None;
+        }
+        let origin = Point::new(line.a * line.c / length_squared, line.b * line.c / length_squared);
+        self.cyrus_beck(origin, Point::new(-line.b, line.a), T::MIN, T::MAX)
+    }
+
+    /// Narrows `t_min..=t_max` to the part of `origin + t * direction` inside the polygon.
+    fn cyrus_beck(&self, origin: Point<T>, direction: Point<T>, t_min: T, t_max: T) -> Option<Clip<T>> {
+        let vertices = &self.vertices;
+        let n = vertices.len();
+        if n < 3 {
+            return None;
+        }
+        let outside = T::from_f64(orientation_sign(vertices));
+        let (mut t0, mut t1) = (t_min, t_max);
+        let (mut entry_edge, mut exit_edge) = (None, None);
+        for i in 0..n {
+            let (a, b) = (vertices[i], vertices[(i + 1) % n]);
+            // How far outside edge i the origin is, and how fast the line moves outwards
+            let distance = -outside * T::from_f64(is_left(a, b, origin));
+            let rate = outside * ((b.y - a.y) * direction.x - (b.x - a.x) * direction.y);
+            if rate == T::ZERO {
+                if distance > T::ZERO {
+                    return None;
+                }
+                continue;
+            }
+            let t = -distance / rate;
+            if rate < T::ZERO {
+                if t > t0 {
+                    t0 = t;
+                    entry_edge = Some(i);
+                }
+            } else if t < t1 {
+                t1 = t;
+                exit_edge = Some(i);
+            }
+            if t0 > t1 {
+                return None;
+            }
+        }
+        let at = |t: T| Point::new(origin.x + t * direction.x, origin.y + t * direction.y);
+        Some(Clip { t0, t1, start: at(t0), end: at(t1), entry_edge, exit_edge })
+    }
+
+    /// Clips the ring `subject`, which may be concave and run either way, to the polygon.
+    /// The result runs the same way as `subject`, and is empty if nothing of it is left.
+    ///
+    /// Where a concave ring leaves the window and comes back, the pieces inside stay joined
+    /// by zero-width stretches along the window's boundary, as usual for Sutherland–Hodgman.
+    /// They fill nothing, so this is fine for rendering; use `Polygon::intersection` for
+    /// separate pieces.
+    pub fn clip_ring(&self, subject: &[Point<T>]) -> Vec<Point<T>> {
+        let window = &self.vertices;
+        let n = window.len();
+        if n < 3 {
+            return Vec::new();
+        }
+        let inside = orientation_sign(window);
+        let mut output = subject.to_vec();
+        for i in 0..n {
+            if output.is_empty() {
+                break;
+            }
+            output = clip_ring_to_half_plane(&output, window[i], window[(i + 1) % n], inside);
+        }
+        if output.len() < 3 {
+            output.clear();
+        }
+        output
+    }
+}
+
+/// The part of `ring` on the inner side of the line `a` → `b`: the left if `inside` is
+/// positive, the right if it is negative. Points on the line count as inside.
+pub(crate) fn clip_ring_to_half_plane<T: Scalar>(ring: &[Point<T>], a: Point<T>, b: Point<T>, inside: f64) -> Vec<Point<T>> {
+    let edge_line = Line::from_points(a, b);
+    let m = ring.len();
+    let mut output = Vec::with_capacity(m + 1);
+    for j in 0..m {
+        let (previous, current) = (ring[(j + m - 1) % m], ring[j]);
+        let (previous_side, current_side) = (inside * is_left(a, b, previous), inside * is_left(a, b, current));
+        // Only strict crossings add a point, so vertices on the line are not doubled
+        if (previous_side < 0.0 && current_side > 0.0) || (previous_side > 0.0 && current_side < 0.0) {
+            output.push(crossing(&edge_line, previous, current, previous_side, current_side));
+        }
+        if current_side >= 0.0 {
+            output.push(current);
+        }
+    }
+    output.dedup();
+    while output.len() > 1 && output.first() == output.last() {
+        output.pop();
+    }
+    output
+}
+
+/// Where the edge `previous` → `current` crosses `line`, given which side of it each end is on.
+fn crossing<T: Scalar>(line: &Line<T>, previous: Point<T>, current: Point<T>, previous_side: f64, current_side: f64) -> Point<T> {
+    intersect_lines(line, &Line::from_points(previous, current)).unwrap_or_else(|| {
+        // Too close to parallel for the determinant, so interpolate instead
+        let t = T::from_f64(previous_side / (previous_side - current_side));
+        Point::new(previous.x + t * (current.x - previous.x), previous.y + t * (current.y - previous.y))
+    })
+}
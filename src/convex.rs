@@ -0,0 +1,282 @@
+use crate::aabb::{bounds_may_touch, Aabb};
+use crate::chasing::intersect_chasing;
+use crate::error::PolyError;
+use crate::polygon::{dedup_consecutive_indices, ring_contains};
+use crate::primitives::{dedup_vertices, is_left, Point, Segment, SegmentIntersection};
+use crate::scalar::Scalar;
+use crate::tolerance::Tolerance;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ConvexPolygon<T> {
+    pub vertices: Vec<Point<T>>,
+}
+
+/// Up to two points where a segment crosses a convex boundary.
+type Crossings<T> = (Option<Point<T>>, Option<Point<T>>);
+
+/// The region shared by two convex polygons, which may be degenerate.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ConvexIntersection<T> {
+    Empty,
+    /// The polygons touch at a single point.
+    Point(Point<T>),
+    /// The polygons share part of an edge.
+    Segment(Segment<T>),
+    Polygon(ConvexPolygon<T>),
+}
+
+impl<T: Scalar> ConvexIntersection<T> {
+    pub fn is_empty(&self) -> bool {
+        matches!(self, ConvexIntersection::Empty)
+    }
+
+    /// The intersection as a polygon, or `None` if it has no area.
+    pub fn into_polygon(self) -> Option<ConvexPolygon<T>> {
+        match self {
+            ConvexIntersection::Polygon(polygon) => Some(polygon),
+            _ => None,
+        }
+    }
+}
+
+fn order_vertices_clockwise<T: Scalar>(vertices: &mut [Point<T>]) {
+    let n = T::from_usize(vertices.len());
+    let centroid = Point {
+        x: vertices.iter().map(|p| p.x).sum::<T>() / n,
+        y: vertices.iter().map(|p| p.y).sum::<T>() / n,
+    };
+    vertices.sort_by(|a, b| {
+        let angle_a = (a.y - centroid.y).atan2(a.x - centroid.x);
+        let angle_b = (b.y - centroid.y).atan2(b.x - centroid.x);
+        angle_a.partial_cmp(&angle_b).unwrap()
+    });
+}
+
+pub fn check_polygon_is_convex<T: Scalar>(vertices: &[Point<T>]) -> bool {
+    validate_convex(vertices).is_ok()
+}
+
+/// Checks the same invariant as `check_polygon_is_convex`, but reports which vertex broke it.
+fn validate_convex<T: Scalar>(vertices: &[Point<T>]) -> Result<(), PolyError> {
+    validate_vertices(vertices)?;
+    match concave_vertex(vertices) {
+        Some(index) => Err(PolyError::NotConvex { index }),
+        None => Ok(()),
+    }
+}
+
+/// Checks there are enough vertices and that they are all finite.
+fn validate_vertices<T: Scalar>(vertices: &[Point<T>]) -> Result<(), PolyError> {
+    let n = vertices.len();
+    if n < 3 {
+        return Err(PolyError::TooFewVertices { count: n });
+    }
+    if let Some(index) = vertices.iter().position(|p| !p.x.is_finite() || !p.y.is_finite()) {
+        return Err(PolyError::NonFinite { index });
+    }
+    Ok(())
+}
+
+/// The first vertex where the ring turns clockwise, if any.
+fn concave_vertex<T: Scalar>(vertices: &[Point<T>]) -> Option<usize> {
+    let n = vertices.len();
+    (0..n).find(|&i| is_left(vertices[i], vertices[(i + 1) % n], vertices[(i + 2) % n]) < 0.0).map(|i| (i + 1) % n)
+}
+
+impl<T: Scalar> ConvexPolygon<T> {
+    /// Builds a polygon from vertices given in counter-clockwise order.
+    ///
+    /// Panics if the vertices do not form a convex polygon; see `try_new`.
+    pub fn new(vertices: &[Point<T>]) -> ConvexPolygon<T> {
+        ConvexPolygon::try_new(vertices).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Builds a polygon from vertices given in counter-clockwise order,
+    /// returning an error instead of panicking when the input is invalid.
+    ///
+    /// Vertices equal within `Tolerance::default()` are merged.
+    pub fn try_new(vertices: &[Point<T>]) -> Result<ConvexPolygon<T>, PolyError> {
+        ConvexPolygon::try_new_with_tolerance(vertices, &Tolerance::default())
+    }
+
+    /// Like `try_new`, merging vertices that are equal within `tolerance`.
+    pub fn try_new_with_tolerance(vertices: &[Point<T>], tolerance: &Tolerance<T>) -> Result<ConvexPolygon<T>, PolyError> {
+        validate_vertices(vertices)?;
+        if concave_vertex(vertices).is_some() {
+            // A near-duplicate can turn the wrong way by a hair, so check again with neighbours
+            // the tolerance merges, reporting the vertex by its index in the input
+            let kept = dedup_consecutive_indices(vertices, tolerance);
+            let ring: Vec<Point<T>> = kept.iter().map(|&i| vertices[i]).collect();
+            if let Some(index) = concave_vertex(&ring) {
+                return Err(PolyError::NotConvex { index: kept[index] });
+            }
+        }
+        let mut vertices_copy = dedup_vertices(vertices, tolerance);
+        if vertices_copy.len() < 3 || vertices_copy.iter().all(|p| is_left(vertices_copy[0], vertices_copy[1], *p) == 0.0) {
+            return Err(PolyError::Degenerate);
+        }
+        order_vertices_clockwise(&mut vertices_copy);
+        Ok(ConvexPolygon { vertices: vertices_copy })
+    }
+
+    /// Converts the vertices to another scalar type.
+    ///
+    /// Narrowing conversions round each coordinate, so the result is not re-validated.
+    pub fn cast<U: Scalar>(&self) -> ConvexPolygon<U> {
+        ConvexPolygon { vertices: self.vertices.iter().map(|p| p.cast()).collect() }
+    }
+
+    pub fn bounds(&self) -> Aabb<T> {
+        Aabb::from_points(&self.vertices)
+    }
+
+    /// Whether `p` is inside the polygon or on its boundary, within `Tolerance::default()`.
+    pub fn is_point_inside(&self, p: Point<T>) -> bool {
+        self.is_point_inside_with_tolerance(p, &Tolerance::default())
+    }
+
+    /// Whether `p` is inside the polygon, on its boundary or within `tolerance` of a vertex.
+    pub fn is_point_inside_with_tolerance(&self, p: Point<T>, tolerance: &Tolerance<T>) -> bool {
+        ring_contains(&self.vertices, p, tolerance)
+    }
+
+    /// Returns the points where `segment` crosses the polygon boundary.
+    ///
+    /// Panics if more than two distinct crossings are found; see `try_intersect_with_segment`.
+    pub fn intersect_with_segment(&self, segment: &Segment<T>) -> Crossings<T> {
+        self.try_intersect_with_segment(segment).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Returns the points where `segment` crosses the polygon boundary, or an error
+    /// if there are more than two, which can only happen for a non-convex vertex list.
+    ///
+    /// Where the segment runs along an edge the ends of the shared stretch are reported.
+    pub fn try_intersect_with_segment(&self, segment: &Segment<T>) -> Result<Crossings<T>, PolyError> {
+        self.try_intersect_with_segment_with_tolerance(segment, &Tolerance::default())
+    }
+
+    /// Like `try_intersect_with_segment`, comparing points within `tolerance`.
+    pub fn try_intersect_with_segment_with_tolerance(&self, segment: &Segment<T>, tolerance: &Tolerance<T>) -> Result<Crossings<T>, PolyError> {
+        self.segment_crossings(segment, tolerance, true)
+    }
+
+    /// The points where `segment` meets the boundary, leaving out the ends of stretches
+    /// it shares with an edge unless `overlaps` is set.
+    fn segment_crossings(&self, segment: &Segment<T>, tolerance: &Tolerance<T>, overlaps: bool) -> Result<Crossings<T>, PolyError> {
+        if !bounds_may_touch(&self.bounds(), &segment.bounds(), tolerance) {
+            return Ok((None, None));
+        }
+        let mut intersections = Vec::new();
+        let n = self.vertices.len();
+        for i in 0..n {
+            let v1 = self.vertices[i];
+            let v2 = self.vertices[(i + 1) % n];
+            let seg = Segment::new(v1, v2);
+            match seg.intersection_with_tolerance(segment, tolerance) {
+                SegmentIntersection::None => {}
+                SegmentIntersection::Point(p) => intersections.push(p),
+                SegmentIntersection::Overlap(shared) if overlaps => intersections.extend([shared.p1, shared.p2]),
+                SegmentIntersection::Overlap(_) => {}
+            }
+        }
+        let intersections = dedup_vertices(&intersections, tolerance);
+        if intersections.len() > 2 {
+            return Err(PolyError::TooManyIntersections { count: intersections.len() });
+        }
+
+        if intersections.is_empty() {
+            Ok((None, None))
+        } else if intersections.len() == 1 || intersections[0] == intersections[1] {
+            Ok((Some(intersections[0]), None))
+        } else {
+            Ok((Some(intersections[0]), Some(intersections[1])))
+        }
+    }
+}
+
+impl<T: Scalar> ConvexPolygon<T> {
+    /// The region shared with `other`, with duplicate points merged and polygon
+    /// vertices in the same counter-clockwise order `new` produces.
+    ///
+    /// Runs in O(n + m) by walking both boundaries together, rather than testing
+    /// every pair of edges like `intersect_convex_polygons`.
+    ///
+    /// Panics if either vertex list is not convex; see `try_intersection`.
+    pub fn intersection(&self, other: &ConvexPolygon<T>) -> ConvexIntersection<T> {
+        self.try_intersection(other).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    pub fn try_intersection(&self, other: &ConvexPolygon<T>) -> Result<ConvexIntersection<T>, PolyError> {
+        self.try_intersection_with_tolerance(other, &Tolerance::default())
+    }
+
+    /// Like `try_intersection`, comparing points within `tolerance`.
+    pub fn try_intersection_with_tolerance(&self, other: &ConvexPolygon<T>, tolerance: &Tolerance<T>) -> Result<ConvexIntersection<T>, PolyError> {
+        validate_convex(&self.vertices)?;
+        validate_convex(&other.vertices)?;
+        if !bounds_may_touch(&self.bounds(), &other.bounds(), tolerance) {
+            return Ok(ConvexIntersection::Empty);
+        }
+        Ok(intersect_chasing(self, other, tolerance))
+    }
+}
+
+impl From<ConvexPolygon<f32>> for ConvexPolygon<f64> {
+    fn from(polygon: ConvexPolygon<f32>) -> ConvexPolygon<f64> {
+        polygon.cast()
+    }
+}
+
+
+/// Returns every point found on the boundary of the intersection, duplicates included.
+/// `ConvexPolygon::intersection` turns these into a single shape.
+///
+/// Panics if either polygon's vertex list is not convex; see `try_intersect_convex_polygons`.
+pub fn intersect_convex_polygons<T: Scalar>(poly_one: &ConvexPolygon<T>, poly_two: &ConvexPolygon<T>) -> Vec<Point<T>> {
+    try_intersect_convex_polygons(poly_one, poly_two).unwrap_or_else(|e| panic!("{e}"))
+}
+
+pub fn try_intersect_convex_polygons<T: Scalar>(poly_one: &ConvexPolygon<T>, poly_two: &ConvexPolygon<T>) -> Result<Vec<Point<T>>, PolyError> {
+    try_intersect_convex_polygons_with_tolerance(poly_one, poly_two, &Tolerance::default())
+}
+
+pub fn try_intersect_convex_polygons_with_tolerance<T: Scalar>(poly_one: &ConvexPolygon<T>, poly_two: &ConvexPolygon<T>, tolerance: &Tolerance<T>) -> Result<Vec<Point<T>>, PolyError> {
+    let mut intersections = Vec::new();
+    if !bounds_may_touch(&poly_one.bounds(), &poly_two.bounds(), tolerance) {
+        return Ok(intersections);
+    }
+    let n1 = poly_one.vertices.len();
+    let n2 = poly_two.vertices.len();
+    for i in 0..n1 {
+        if poly_two.is_point_inside_with_tolerance(poly_one.vertices[i], tolerance) {
+            intersections.push(poly_one.vertices[i]);
+        }
+        let v1 = poly_one.vertices[i];
+        let v2 = poly_one.vertices[(i + 1) % n1];
+        let seg = Segment::new(v1, v2);
+        // Edges shared with the other polygon end at vertices, which are added above.
+        let (p1, p2) = poly_two.segment_crossings(&seg, tolerance, false)?;
+        if let Some(p) = p1 {
+            intersections.push(p);
+        }
+        if let Some(p) = p2 {
+            intersections.push(p);
+        }
+    }
+    for i in 0..n2 {
+        if poly_one.is_point_inside_with_tolerance(poly_two.vertices[i], tolerance) {
+            intersections.push(poly_two.vertices[i]);
+        }
+        let v1 = poly_two.vertices[i];
+        let v2 = poly_two.vertices[(i + 1) % n2];
+        let seg = Segment::new(v1, v2);
+        let (p1, p2) = poly_one.segment_crossings(&seg, tolerance, false)?;
+        if let Some(p) = p1 {
+            intersections.push(p);
+        }
+        if let Some(p) = p2 {
+            intersections.push(p);
+        }
+    }
+    Ok(intersections)
+}
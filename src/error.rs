@@ -0,0 +1,52 @@
+use std::fmt;
+
+/// Errors reported by the fallible polygon constructors and queries.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PolyError {
+    /// Fewer than three vertices were supplied.
+    TooFewVertices { count: usize },
+    /// The vertex at `index` has a NaN or infinite coordinate.
+    NonFinite { index: usize },
+    /// The boundary turns clockwise at the vertex at `index`.
+    NotConvex { index: usize },
+    /// The vertices do not enclose any area (they coincide or are collinear).
+    Degenerate,
+    /// A segment crossed a polygon boundary in more than two distinct points.
+    TooManyIntersections { count: usize },
+    /// Edges `first` and `second` of a polygon touch or cross. Edge `i` runs from
+    /// vertex `i` to the next one.
+    SelfIntersecting { first: usize, second: usize },
+    /// The hole at `index` is not inside the exterior ring.
+    HoleOutsideExterior { index: usize },
+    /// The holes at `first` and `second` overlap.
+    OverlappingHoles { first: usize, second: usize },
+    /// The constraint edges at `first` and `second` of a constrained triangulation cross.
+    CrossingConstraints { first: usize, second: usize },
+    /// The constraint edge at `index` refers to a point that does not exist.
+    InvalidConstraint { index: usize },
+}
+
+impl fmt::Display for PolyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolyError::TooFewVertices { count } => {
+                write!(f, "polygon needs at least 3 vertices, got {count}")
+            }
+            PolyError::NonFinite { index } => write!(f, "vertex {index} has a non-finite coordinate"),
+            PolyError::NotConvex { index } => write!(f, "polygon is not convex at vertex {index}"),
+            PolyError::Degenerate => write!(f, "polygon vertices do not enclose any area"),
+            PolyError::TooManyIntersections { count } => {
+                write!(f, "segment crosses convex polygon boundary {count} times")
+            }
+            PolyError::SelfIntersecting { first, second } => {
+                write!(f, "polygon edges {first} and {second} intersect")
+            }
+            PolyError::HoleOutsideExterior { index } => write!(f, "hole {index} is not inside the exterior"),
+            PolyError::OverlappingHoles { first, second } => write!(f, "holes {first} and {second} overlap"),
+            PolyError::CrossingConstraints { first, second } => write!(f, "constraint edges {first} and {second} cross"),
+            PolyError::InvalidConstraint { index } => write!(f, "constraint edge {index} refers to a point that does not exist"),
+        }
+    }
+}
+
+impl std::error::Error for PolyError {}
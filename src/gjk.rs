@@ -0,0 +1,265 @@
+//! Distance and penetration queries between convex shapes described only by
+//! their support functions.
+//!
+//! GJK (Gilbert, Johnson and Keerthi, 1988) walks a simplex inside the Minkowski
+//! difference `A - B` towards the origin: the distance from the origin to `A - B`
+//! is the distance between the shapes, and the origin lies inside it exactly when
+//! they overlap. In that case EPA (van den Bergen, 2001) grows a polygon inside
+//! `A - B` out to the boundary nearest the origin, which gives the penetration.
+
+use crate::convex::ConvexPolygon;
+use crate::primitives::{is_left, outward_normal, Point, Segment};
+use crate::scalar::Scalar;
+
+const MAX_ITERATIONS: usize = 64;
+
+/// A convex shape that can report its farthest point in any direction.
+pub trait Support<T: Scalar> {
+    /// The point of the shape with the largest dot product with `direction`. Any of
+    /// several equally far points will do.
+    fn support(&self, direction: Point<T>) -> Point<T>;
+}
+
+impl<T: Scalar> Support<T> for Point<T> {
+    fn support(&self, _direction: Point<T>) -> Point<T> {
+        *self
+    }
+}
+
+impl<T: Scalar> Support<T> for Segment<T> {
+    fn support(&self, direction: Point<T>) -> Point<T> {
+        if dot(self.p2, direction) > dot(self.p1, direction) {
+            self.p2
+        } else {
+            self.p1
+        }
+    }
+}
+
+impl<T: Scalar> Support<T> for ConvexPolygon<T> {
+    fn support(&self, direction: Point<T>) -> Point<T> {
+        self.vertices
+            .iter()
+            .copied()
+            .reduce(|best, v| if dot(v, direction) > dot(best, direction) { v } else { best })
+            .unwrap_or(Point::new(T::ZERO, T::ZERO))
+    }
+}
+
+fn dot<T: Scalar>(p: Point<T>, q: Point<T>) -> T {
+    p.x * q.x + p.y * q.y
+}
+
+fn sub<T: Scalar>(p: Point<T>, q: Point<T>) -> Point<T> {
+    Point::new(p.x - q.x, p.y - q.y)
+}
+
+fn neg<T: Scalar>(p: Point<T>) -> Point<T> {
+    Point::new(-p.x, -p.y)
+}
+
+/// A point of `A - B` along with the points of `A` and `B` it came from.
+#[derive(Clone, Copy)]
+struct Vertex<T> {
+    w: Point<T>,
+    a: Point<T>,
+    b: Point<T>,
+}
+
+fn support_difference<T: Scalar>(a: &impl Support<T>, b: &impl Support<T>, direction: Point<T>) -> Vertex<T> {
+    let (pa, pb) = (a.support(direction), b.support(neg(direction)));
+    Vertex { w: sub(pa, pb), a: pa, b: pb }
+}
+
+/// The vertices of `simplex` that the point nearest the origin depends on, with their
+/// barycentric weights.
+fn nearest_in_simplex<T: Scalar>(simplex: &[Vertex<T>]) -> Vec<(Vertex<T>, T)> {
+    match *simplex {
+        [p] => vec![(p, T::ONE)],
+        [p, q] => nearest_on_segment(p, q),
+        [a, b, c] => nearest_in_triangle(a, b, c),
+        _ => unreachable!("a simplex in the plane has one to three vertices"),
+    }
+}
+
+fn nearest_on_segment<T: Scalar>(p: Vertex<T>, q: Vertex<T>) -> Vec<(Vertex<T>, T)> {
+    let d = sub(q.w, p.w);
+    let length_squared = dot(d, d);
+    if length_squared == T::ZERO {
+        return vec![(p, T::ONE)];
+    }
+    let t = -dot(p.w, d) / length_squared;
+    if t <= T::ZERO {
+        vec![(p, T::ONE)]
+    } else if t >= T::ONE {
+        vec![(q, T::ONE)]
+    } else {
+        vec![(p, T::ONE - t), (q, t)]
+    }
+}
+
+/// After Ericson, *Real-Time Collision Detection*, section 5.1.5, with the query point at the origin.
+fn nearest_in_triangle<T: Scalar>(a: Vertex<T>, b: Vertex<T>, c: Vertex<T>) -> Vec<(Vertex<T>, T)> {
+    let (ab, ac) = (sub(b.w, a.w), sub(c.w, a.w));
+    let (d1, d2) = (-dot(ab, a.w), -dot(ac, a.w));
+    if d1 <= T::ZERO && d2 <= T::ZERO {
+        return vec![(a, T::ONE)];
+    }
+    let (d3, d4) = (-dot(ab, b.w), -dot(ac, b.w));
+    if d3 >= T::ZERO && d4 <= d3 {
+        return vec![(b, T::ONE)];
+    }
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= T::ZERO && d1 >= T::ZERO && d3 <= T::ZERO {
+        let t = d1 / (d1 - d3);
+        return vec![(a, T::ONE - t), (b, t)];
+    }
+    let (d5, d6) = (-dot(ab, c.w), -dot(ac, c.w));
+    if d6 >= T::ZERO && d5 <= d6 {
+        return vec![(c, T::ONE)];
+    }
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= T::ZERO && d2 >= T::ZERO && d6 <= T::ZERO {
+        let t = d2 / (d2 - d6);
+        return vec![(a, T::ONE - t), (c, t)];
+    }
+    let va = d3 * d6 - d5 * d4;
+    if va <= T::ZERO && d4 - d3 >= T::ZERO && d5 - d6 >= T::ZERO {
+        let t = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+        return vec![(b, T::ONE - t), (c, t)];
+    }
+    let sum = va + vb + vc;
+    if sum <= T::ZERO {
+        // A flat triangle; the nearest point is on one of its edges.
+        let distance_squared = |weighted: &Vec<(Vertex<T>, T)>| {
+            let p = combine(weighted, |v| v.w);
+            dot(p, p)
+        };
+        return [nearest_on_segment(a, b), nearest_on_segment(b, c), nearest_on_segment(a, c)]
+            .into_iter()
+            .min_by(|x, y| distance_squared(x).partial_cmp(&distance_squared(y)).unwrap())
+            .unwrap();
+    }
+    vec![(a, va / sum), (b, vb / sum), (c, vc / sum)]
+}
+
+/// The weighted sum of one of the points held by each vertex.
+fn combine<T: Scalar>(weighted: &[(Vertex<T>, T)], point: impl Fn(&Vertex<T>) -> Point<T>) -> Point<T> {
+    weighted.iter().fold(Point::new(T::ZERO, T::ZERO), |sum, (v, weight)| {
+        let p = point(v);
+        Point::new(sum.x + p.x * *weight, sum.y + p.y * *weight)
+    })
+}
+
+enum Gjk<T> {
+    /// The nearest points of the two shapes.
+    Separated(Point<T>, Point<T>),
+    /// A simplex inside `A - B` that contains the origin, or has it on its boundary.
+    Overlapping(Vec<Vertex<T>>),
+}
+
+fn gjk<T: Scalar>(a: &impl Support<T>, b: &impl Support<T>) -> Gjk<T> {
+    let tolerance = T::EPSILON * T::from_f64(100.0);
+    let mut simplex = vec![(support_difference(a, b, Point::new(T::ONE, T::ZERO)), T::ONE)];
+    for _ in 0..MAX_ITERATIONS {
+        let v = combine(&simplex, |v| v.w);
+        let vv = dot(v, v);
+        let largest = simplex.iter().map(|(s, _)| dot(s.w, s.w)).fold(T::ZERO, T::max);
+        if vv <= tolerance * T::EPSILON * largest {
+            return Gjk::Overlapping(simplex.into_iter().map(|(s, _)| s).collect());
+        }
+        let next = support_difference(a, b, neg(v));
+        // Stop once the new point brings `A - B` no closer than `v` already is.
+        if vv - dot(v, next.w) <= tolerance * vv || simplex.iter().any(|(s, _)| s.w == next.w) {
+            break;
+        }
+        let mut vertices: Vec<Vertex<T>> = simplex.iter().map(|(s, _)| *s).collect();
+        vertices.push(next);
+        simplex = nearest_in_simplex(&vertices);
+        if simplex.len() == 3 {
+            return Gjk::Overlapping(simplex.into_iter().map(|(s, _)| s).collect());
+        }
+    }
+    Gjk::Separated(combine(&simplex, |v| v.a), combine(&simplex, |v| v.b))
+}
+
+/// The nearest points of `a` and `b`, or `None` if the shapes overlap.
+///
+/// Shapes that only touch may come out either way, as touching points or as `None`.
+pub fn gjk_closest_points<T: Scalar>(a: &impl Support<T>, b: &impl Support<T>) -> Option<(Point<T>, Point<T>)> {
+    match gjk(a, b) {
+        Gjk::Separated(pa, pb) => Some((pa, pb)),
+        Gjk::Overlapping(_) => None,
+    }
+}
+
+/// The distance between `a` and `b`, which is zero if they overlap.
+pub fn gjk_distance<T: Scalar>(a: &impl Support<T>, b: &impl Support<T>) -> T {
+    gjk_closest_points(a, b).map_or(T::ZERO, |(pa, pb)| pa.distance(pb))
+}
+
+/// The minimum translation that separates overlapping shapes, as a unit `normal` and a
+/// `depth`, or `None` if they do not overlap. Moving `b` by `normal * depth` leaves the
+/// shapes touching, as with `ConvexPolygon::penetration`.
+pub fn epa_penetration<T: Scalar>(a: &impl Support<T>, b: &impl Support<T>) -> Option<(Point<T>, T)> {
+    let mut polytope = match gjk(a, b) {
+        Gjk::Separated(..) => return None,
+        Gjk::Overlapping(simplex) => simplex,
+    };
+
+    // Touching shapes can leave GJK with a point or an edge; grow it to a triangle.
+    let axes = [(T::ONE, T::ZERO), (-T::ONE, T::ZERO), (T::ZERO, T::ONE), (T::ZERO, -T::ONE)];
+    for (x, y) in axes {
+        if polytope.len() != 1 {
+            break;
+        }
+        let v = support_difference(a, b, Point::new(x, y));
+        if v.w != polytope[0].w {
+            polytope.push(v);
+        }
+    }
+    if polytope.len() == 2 {
+        let d = sub(polytope[1].w, polytope[0].w);
+        for normal in [Point::new(-d.y, d.x), Point::new(d.y, -d.x)] {
+            let v = support_difference(a, b, normal);
+            if is_left(polytope[0].w, polytope[1].w, v.w) != 0.0 {
+                polytope.push(v);
+                break;
+            }
+        }
+    }
+    if polytope.len() < 3 {
+        // `A - B` is a point or a segment through the origin, so both shapes are flat.
+        let d = if polytope.len() == 2 { sub(polytope[1].w, polytope[0].w) } else { Point::new(T::ZERO, T::ONE) };
+        return Some((outward_normal(d, Point::new(T::ZERO, T::ZERO)), T::ZERO));
+    }
+    if is_left(polytope[0].w, polytope[1].w, polytope[2].w) < 0.0 {
+        polytope.swap(1, 2);
+    }
+
+    let tolerance = T::EPSILON * T::from_f64(100.0);
+    let mut best = (Point::new(T::ZERO, T::ONE), T::ZERO);
+    for _ in 0..MAX_ITERATIONS {
+        // The edge nearest the origin, with its outward normal
+        let n = polytope.len();
+        let (index, normal, distance) = (0..n)
+            .filter_map(|i| {
+                let (p, q) = (polytope[i].w, polytope[(i + 1) % n].w);
+                if p == q {
+                    return None;
+                }
+                let normal = outward_normal(p, q);
+                Some((i, normal, dot(normal, p)))
+            })
+            .min_by(|x, y| x.2.partial_cmp(&y.2).unwrap())?;
+        best = (normal, distance.max(T::ZERO));
+
+        let next = support_difference(a, b, normal);
+        let reach = dot(normal, next.w);
+        if reach - distance <= tolerance * reach.abs().max(distance.abs()) || polytope.iter().any(|v| v.w == next.w) {
+            break;
+        }
+        polytope.insert(index + 1, next);
+    }
+    Some(best)
+}
@@ -0,0 +1,199 @@
+//! Convex hulls of arbitrary point sets, built either all at once with Andrew's
+//! monotone chain or one point at a time with `HullBuilder`.
+
+use crate::convex::ConvexPolygon;
+use crate::error::PolyError;
+use crate::polygon::leftmost_lowest;
+use crate::primitives::{is_left, Point};
+use crate::scalar::Scalar;
+
+fn check_finite<T: Scalar>(points: &[Point<T>]) -> Result<(), PolyError> {
+    match points.iter().position(|p| !p.x.is_finite() || !p.y.is_finite()) {
+        Some(index) => Err(PolyError::NonFinite { index }),
+        None => Ok(()),
+    }
+}
+
+fn is_degenerate<T: Scalar>(vertices: &[Point<T>]) -> bool {
+    vertices.len() < 3 || vertices.iter().all(|p| is_left(vertices[0], vertices[1], *p) == 0.0)
+}
+
+impl<T: Scalar> ConvexPolygon<T> {
+    /// The convex hull of `points`, in any order.
+    ///
+    /// Panics if there are too few points or they are all collinear; see `try_hull`.
+    pub fn hull(points: &[Point<T>]) -> ConvexPolygon<T> {
+        ConvexPolygon::try_hull(points).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// The convex hull of `points`, returning an error instead of panicking when the
+    /// points do not enclose any area.
+    ///
+    /// Points that lie along a hull edge are left out; see `try_hull_with_collinear`.
+    pub fn try_hull(points: &[Point<T>]) -> Result<ConvexPolygon<T>, PolyError> {
+        ConvexPolygon::try_hull_with_collinear(points, false)
+    }
+
+    /// Like `try_hull`, but when `keep_collinear` is set points lying exactly on a hull
+    /// edge are kept as vertices.
+    ///
+    /// Vertices run counter-clockwise from the leftmost-lowest point. Sorting the points
+    /// dominates, so this takes O(n log n) time.
+    pub fn try_hull_with_collinear(points: &[Point<T>], keep_collinear: bool) -> Result<ConvexPolygon<T>, PolyError> {
+        if points.len() < 3 {
+            return Err(PolyError::TooFewVertices { count: points.len() });
+        }
+        check_finite(points)?;
+        let mut sorted = points.to_vec();
+        sorted.sort_by(|a, b| (a.x, a.y).partial_cmp(&(b.x, b.y)).unwrap());
+        sorted.dedup();
+
+        let discard = |turn: f64| if keep_collinear { turn < 0.0 } else { turn <= 0.0 };
+        let mut lower: Vec<Point<T>> = Vec::new();
+        for p in &sorted {
+            while lower.len() >= 2 && discard(is_left(lower[lower.len() - 2], lower[lower.len() - 1], *p)) {
+                lower.pop();
+            }
+            lower.push(*p);
+        }
+        let mut upper: Vec<Point<T>> = Vec::new();
+        for p in sorted.iter().rev() {
+            while upper.len() >= 2 && discard(is_left(upper[upper.len() - 2], upper[upper.len() - 1], *p)) {
+                upper.pop();
+            }
+            upper.push(*p);
+        }
+        // Each chain ends where the other begins.
+        lower.pop();
+        upper.pop();
+        lower.extend(upper);
+
+        if is_degenerate(&lower) {
+            return Err(PolyError::Degenerate);
+        }
+        Ok(ConvexPolygon { vertices: lower })
+    }
+}
+
+/// Builds a convex hull from points that arrive one at a time, keeping only the
+/// current hull rather than every point seen.
+///
+/// Points inside the hull or along its edges are dropped as they arrive. Each
+/// point costs O(h) for a hull of h vertices.
+#[derive(Debug, PartialEq, Clone)]
+pub struct HullBuilder<T> {
+    /// The hull so far, counter-clockwise, or while every point has been collinear
+    /// the (at most two) extreme points.
+    vertices: Vec<Point<T>>,
+    count: usize,
+}
+
+impl<T: Scalar> HullBuilder<T> {
+    pub fn new() -> HullBuilder<T> {
+        HullBuilder { vertices: Vec::new(), count: 0 }
+    }
+
+    /// The number of points added so far.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Adds a point to the hull. Returns an error, leaving the hull unchanged, if it has a
+    /// non-finite coordinate; the error's index counts every point added before it.
+    pub fn add(&mut self, p: Point<T>) -> Result<(), PolyError> {
+        if !p.x.is_finite() || !p.y.is_finite() {
+            return Err(PolyError::NonFinite { index: self.count });
+        }
+        self.count += 1;
+        if is_degenerate(&self.vertices) {
+            self.add_while_collinear(p);
+        } else {
+            self.add_to_polygon(p);
+        }
+        Ok(())
+    }
+
+    /// Adds every point in `points`, stopping at the first error.
+    pub fn extend(&mut self, points: impl IntoIterator<Item = Point<T>>) -> Result<(), PolyError> {
+        points.into_iter().try_for_each(|p| self.add(p))
+    }
+
+    fn add_while_collinear(&mut self, p: Point<T>) {
+        match self.vertices[..] {
+            [] => self.vertices.push(p),
+            [a] => {
+                if a != p {
+                    self.vertices.push(p);
+                }
+            }
+            [a, b, ..] => {
+                let turn = is_left(a, b, p);
+                if turn > 0.0 {
+                    self.vertices = vec![a, b, p];
+                } else if turn < 0.0 {
+                    self.vertices = vec![b, a, p];
+                } else {
+                    // Keep whichever two of the three points are farthest apart.
+                    let (ab, ap, bp) = (a.distance(b), a.distance(p), b.distance(p));
+                    if ap > ab && ap >= bp {
+                        self.vertices = vec![a, p];
+                    } else if bp > ab {
+                        self.vertices = vec![p, b];
+                    }
+                }
+            }
+        }
+        if self.vertices.len() == 3 {
+            self.start_at_leftmost_lowest();
+        }
+    }
+
+    fn add_to_polygon(&mut self, p: Point<T>) {
+        let n = self.vertices.len();
+        let turns: Vec<f64> = (0..n).map(|i| is_left(self.vertices[i], self.vertices[(i + 1) % n], p)).collect();
+        if turns.iter().all(|&turn| turn >= 0.0) {
+            // Inside the hull or on its boundary
+            return;
+        }
+        // The edges p can see, counting those it is in line with, form one run; replace the
+        // vertices inside that run with p.
+        let visible = |i: usize| turns[i % n] <= 0.0;
+        let first = (0..n).find(|&i| visible(i) && !visible(i + n - 1)).unwrap();
+        let mut last = first;
+        while visible(last + 1) {
+            last += 1;
+        }
+        let kept = n - (last - first);
+        let mut vertices: Vec<Point<T>> = (0..kept).map(|k| self.vertices[(last + 1 + k) % n]).collect();
+        vertices.push(p);
+        self.vertices = vertices;
+        self.start_at_leftmost_lowest();
+    }
+
+    fn start_at_leftmost_lowest(&mut self) {
+        let start = leftmost_lowest(&self.vertices);
+        self.vertices.rotate_left(start);
+    }
+
+    /// The hull of the points added so far, with vertices in the same order as
+    /// `ConvexPolygon::try_hull` gives.
+    pub fn hull(&self) -> Result<ConvexPolygon<T>, PolyError> {
+        if self.count < 3 {
+            return Err(PolyError::TooFewVertices { count: self.count });
+        }
+        if is_degenerate(&self.vertices) {
+            return Err(PolyError::Degenerate);
+        }
+        Ok(ConvexPolygon { vertices: self.vertices.clone() })
+    }
+}
+
+impl<T: Scalar> Default for HullBuilder<T> {
+    fn default() -> HullBuilder<T> {
+        HullBuilder::new()
+    }
+}
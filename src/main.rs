@@ -1,5 +1,5 @@
 
-use polyrust::{generate_svg_from_polygons, intersect_convex_polygons, ConvexPolygon, Point, Segment};
+use polyrust::{generate_svg_from_polygons, ConvexPolygon, Point, Segment};
 use polyrust::intersect_line_segments;
 
 fn main() {
@@ -18,23 +18,23 @@ fn main() {
     }
 
     // create two polygons
-    let poly_one = ConvexPolygon::new(&vec![
+    let poly_one = ConvexPolygon::new(&[
         Point { x: 0.0, y: 0.0 },
         Point { x: 2.0, y: 0.0 },
         Point { x: 2.0, y: 2.0 },
         Point { x: 0.0, y: 2.0 },
     ]);
-    let poly_two = ConvexPolygon::new(&vec![
+    let poly_two = ConvexPolygon::new(&[
         Point { x: 1.0, y: 1.0 },
         Point { x: 3.0, y: 1.0 },
         Point { x: 3.0, y: 3.0 },
         // Point { x: 1.0, y: 3.0 },
     ]);
     // calculate the intersection of the two polygons
-    let intersection_points = intersect_convex_polygons(&poly_one, &poly_two);
-    let poly_three = ConvexPolygon::new(&intersection_points);
-    // print the intersection points
-    println!("Intersection points: {:?}", intersection_points);
+    let intersection = poly_one.intersection(&poly_two);
+    // print the intersection
+    println!("Intersection: {:?}", intersection);
+    let poly_three = intersection.into_polygon().expect("Polygons should overlap");
     
     let pad = 0.1;
     let min = Point { x: 0.0 - pad, y: 0.0 - pad};
@@ -64,8 +64,7 @@ fn main() {
     let polygon1 = ConvexPolygon::new(&vertices1);
     let polygon2 = ConvexPolygon::new(&vertices2);
     
-    let intersection = intersect_convex_polygons(&polygon1, &polygon2);
-    let inter_poly = ConvexPolygon::new(&intersection);
+    let inter_poly = polygon1.intersection(&polygon2).into_polygon().expect("Polygons should overlap");
     let svg = generate_svg_from_polygons(&vec![&polygon1, &polygon2, &inter_poly],
         &vec!["blue", "red", "green"],
         300, 200, None);
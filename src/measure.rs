@@ -0,0 +1,120 @@
+//! Mass properties of convex polygons: area, perimeter, centroid and second moments.
+
+use crate::convex::ConvexPolygon;
+use crate::polygon::{ring_perimeter, ring_twice_area};
+use crate::predicates::Orientation;
+use crate::primitives::Point;
+use crate::scalar::Scalar;
+
+/// Second moments of area about the centroid, treating the polygon as a lamina of unit density.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct SecondMoments<T> {
+    /// ∫ (y - cy)² dA, resistance to bending about the horizontal axis.
+    pub ixx: T,
+    /// ∫ (x - cx)² dA, resistance to bending about the vertical axis.
+    pub iyy: T,
+    /// ∫ (x - cx)(y - cy) dA, zero when either axis is an axis of symmetry.
+    pub ixy: T,
+}
+
+impl<T: Scalar> SecondMoments<T> {
+    /// The polar moment `ixx + iyy`, about an axis through the centroid perpendicular to the plane.
+    pub fn polar(&self) -> T {
+        self.ixx + self.iyy
+    }
+}
+
+/// Edge terms of the shoelace formula, with coordinates taken relative to the first
+/// vertex so that polygons far from the origin keep their precision.
+fn shifted_edges<T: Scalar>(vertices: &[Point<T>]) -> impl Iterator<Item = (Point<T>, Point<T>, T)> + '_ {
+    let n = vertices.len();
+    let origin = vertices[0];
+    let shift = move |p: Point<T>| Point::new(p.x - origin.x, p.y - origin.y);
+    (0..n).map(move |i| {
+        let (a, b) = (shift(vertices[i]), shift(vertices[(i + 1) % n]));
+        (a, b, a.x * b.y - b.x * a.y)
+    })
+}
+
+impl<T: Scalar> ConvexPolygon<T> {
+    /// The area, positive when the vertices run counter-clockwise and negative when clockwise.
+    pub fn signed_area(&self) -> T {
+        ring_twice_area(&self.vertices) / T::TWO
+    }
+
+    pub fn area(&self) -> T {
+        self.signed_area().abs()
+    }
+
+    /// Which way the vertices run, from the sign of the area.
+    pub fn orientation(&self) -> Orientation {
+        let area = self.signed_area();
+        if area > T::ZERO {
+            Orientation::CounterClockwise
+        } else if area < T::ZERO {
+            Orientation::Clockwise
+        } else {
+            Orientation::Collinear
+        }
+    }
+
+    /// The length of the boundary.
+    pub fn perimeter(&self) -> T {
+        ring_perimeter(&self.vertices)
+    }
+
+    /// The centre of mass of the enclosed area.
+    ///
+    /// Falls back to the average of the vertices if they enclose no area.
+    pub fn centroid(&self) -> Point<T> {
+        let n = self.vertices.len();
+        if n == 0 {
+            return Point::new(T::ZERO, T::ZERO);
+        }
+        let twice_area = ring_twice_area(&self.vertices);
+        let origin = self.vertices[0];
+        if twice_area == T::ZERO {
+            let count = T::from_usize(n);
+            return Point::new(
+                self.vertices.iter().map(|p| p.x).sum::<T>() / count,
+                self.vertices.iter().map(|p| p.y).sum::<T>() / count,
+            );
+        }
+        let (mut cx, mut cy) = (T::ZERO, T::ZERO);
+        for (a, b, cross) in shifted_edges(&self.vertices) {
+            cx += (a.x + b.x) * cross;
+            cy += (a.y + b.y) * cross;
+        }
+        let six_area = T::from_f64(3.0) * twice_area;
+        Point::new(origin.x + cx / six_area, origin.y + cy / six_area)
+    }
+
+    /// The second moments of area about the centroid. They are the same whichever way
+    /// the vertices run.
+    pub fn second_moments(&self) -> SecondMoments<T> {
+        if self.vertices.is_empty() {
+            return SecondMoments { ixx: T::ZERO, iyy: T::ZERO, ixy: T::ZERO };
+        }
+        let (mut area, mut cx, mut cy) = (T::ZERO, T::ZERO, T::ZERO);
+        let (mut ixx, mut iyy, mut ixy) = (T::ZERO, T::ZERO, T::ZERO);
+        for (a, b, cross) in shifted_edges(&self.vertices) {
+            area += cross;
+            cx += (a.x + b.x) * cross;
+            cy += (a.y + b.y) * cross;
+            ixx += (a.y * a.y + a.y * b.y + b.y * b.y) * cross;
+            iyy += (a.x * a.x + a.x * b.x + b.x * b.x) * cross;
+            ixy += (a.x * b.y + T::TWO * a.x * a.y + T::TWO * b.x * b.y + b.x * a.y) * cross;
+        }
+        if area == T::ZERO {
+            return SecondMoments { ixx: T::ZERO, iyy: T::ZERO, ixy: T::ZERO };
+        }
+        let area = area / T::TWO;
+        let (cx, cy) = (cx / (T::from_f64(6.0) * area), cy / (T::from_f64(6.0) * area));
+        // Moments about the first vertex, moved to the centroid with the parallel axis theorem
+        let ixx = ixx / T::from_f64(12.0) - area * cy * cy;
+        let iyy = iyy / T::from_f64(12.0) - area * cx * cx;
+        let ixy = ixy / T::from_f64(24.0) - area * cx * cy;
+        let sign = if area < T::ZERO { -T::ONE } else { T::ONE };
+        SecondMoments { ixx: sign * ixx, iyy: sign * iyy, ixy: sign * ixy }
+    }
+}
@@ -0,0 +1,515 @@
+//! Delaunay triangulations of point sets, optionally constrained to keep given
+//! edges, stored as half-edge meshes.
+//!
+//! Points are inserted in order of x and then y, so each one lies outside the
+//! triangulation of those before it. It is joined to the hull edges it can see,
+//! and Lawson flips then restore the Delaunay property using the exact `incircle`
+//! predicate. Constraints are added afterwards: the edges each one crosses are
+//! flipped out of its way as in Sloan's algorithm, and the new edges are made
+//! Delaunay again everywhere except across constraints.
+
+use std::collections::VecDeque;
+
+use crate::error::PolyError;
+use crate::multipolygon::PolygonWithHoles;
+use crate::predicates::incircle;
+use crate::primitives::{is_left, Point};
+use crate::scalar::Scalar;
+
+/// One side of an edge of a mesh triangle.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct HalfEdge {
+    /// The vertex the half-edge starts from. It ends where the next one in its triangle starts.
+    pub origin: usize,
+    /// The other side of the same edge, in the neighbouring triangle, or `None` on the boundary.
+    pub twin: Option<usize>,
+    /// Whether the edge is a constraint of a constrained triangulation.
+    pub constrained: bool,
+}
+
+/// A triangle mesh stored as half-edges. Half-edges `3 * t`, `3 * t + 1` and `3 * t + 2`
+/// are the sides of triangle `t`, running counter-clockwise around it.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Mesh<T> {
+    pub vertices: Vec<Point<T>>,
+    pub half_edges: Vec<HalfEdge>,
+}
+
+fn next(edge: usize) -> usize {
+    if edge % 3 == 2 {
+        edge - 2
+    } else {
+        edge + 1
+    }
+}
+
+fn prev(edge: usize) -> usize {
+    if edge.is_multiple_of(3) {
+        edge + 2
+    } else {
+        edge - 1
+    }
+}
+
+/// Whether `a` and `b` are both nonzero and of opposite signs.
+fn strictly_opposite(a: f64, b: f64) -> bool {
+    (a > 0.0 && b < 0.0) || (a < 0.0 && b > 0.0)
+}
+
+impl<T: Scalar> Mesh<T> {
+    /// The Delaunay triangulation of `points`.
+    ///
+    /// Panics if there are too few points or they are all collinear; see `try_delaunay`.
+    pub fn delaunay(points: &[Point<T>]) -> Mesh<T> {
+        Mesh::try_delaunay(points).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// The Delaunay triangulation of `points`, returning an error instead of panicking when
+    /// they do not enclose any area.
+    ///
+    /// No point lies strictly inside the circumcircle of any triangle, and the triangles
+    /// cover the convex hull of the points. Where four or more points lie on one circle any
+    /// of the ways of splitting them up may be returned. `vertices` keeps the points as given;
+    /// a repeated point is only used at its first index.
+    pub fn try_delaunay(points: &[Point<T>]) -> Result<Mesh<T>, PolyError> {
+        Mesh::try_delaunay_with_edges(points, &[])
+    }
+
+    /// Like `try_delaunay`, but keeping the edges in `constraints`, given as pairs of indices
+    /// into `points`, and marking them as constrained. Other edges are Delaunay except across
+    /// constraints. A constraint running through another point is split there.
+    ///
+    /// Returns `PolyError::CrossingConstraints` if two constraints cross, and
+    /// `PolyError::InvalidConstraint` if one refers to a point that does not exist.
+    pub fn try_delaunay_with_edges(points: &[Point<T>], constraints: &[(usize, usize)]) -> Result<Mesh<T>, PolyError> {
+        let builder = Builder::build(points, constraints)?;
+        let keep = vec![true; builder.origin.len() / 3];
+        Ok(builder.into_mesh(&keep))
+    }
+
+    /// The constrained Delaunay triangulation of the inside of `polygon`, keeping the edges
+    /// of its exterior and holes.
+    ///
+    /// Panics if the rings cross; see `try_constrained_delaunay`.
+    pub fn constrained_delaunay(polygon: &PolygonWithHoles<T>) -> Mesh<T> {
+        Mesh::try_constrained_delaunay(polygon).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// The constrained Delaunay triangulation of the inside of `polygon`, returning an error
+    /// instead of panicking when its rings cross, which a validated polygon can only do
+    /// within its tolerance.
+    ///
+    /// `vertices` holds the exterior's vertices followed by those of each hole in turn. The
+    /// constraints are numbered the same way, with edge `i` of a ring running from its vertex
+    /// `i` to the next.
+    pub fn try_constrained_delaunay(polygon: &PolygonWithHoles<T>) -> Result<Mesh<T>, PolyError> {
+        let mut points = Vec::new();
+        let mut constraints = Vec::new();
+        for ring in std::iter::once(&polygon.exterior).chain(&polygon.interiors) {
+            let (offset, n) = (points.len(), ring.vertices.len());
+            points.extend_from_slice(&ring.vertices);
+            constraints.extend((0..n).map(|i| (offset + i, offset + (i + 1) % n)));
+        }
+        let builder = Builder::build(&points, &constraints)?;
+        let inside = builder.inside_constraints();
+        Ok(builder.into_mesh(&inside))
+    }
+
+    pub fn triangle_count(&self) -> usize {
+        self.half_edges.len() / 3
+    }
+
+    /// The vertex indices of each triangle, counter-clockwise.
+    pub fn triangles(&self) -> Vec<[usize; 3]> {
+        self.half_edges.chunks(3).map(|sides| [sides[0].origin, sides[1].origin, sides[2].origin]).collect()
+    }
+
+    /// The half-edge after `edge` counter-clockwise around its triangle.
+    pub fn next(&self, edge: usize) -> usize {
+        next(edge)
+    }
+
+    /// The half-edge before `edge` counter-clockwise around its triangle.
+    pub fn prev(&self, edge: usize) -> usize {
+        prev(edge)
+    }
+
+    /// The vertex `edge` ends at.
+    pub fn destination(&self, edge: usize) -> usize {
+        self.half_edges[next(edge)].origin
+    }
+}
+
+/// A triangulation under construction, with what is needed to grow and flip it.
+struct Builder<'a, T> {
+    points: &'a [Point<T>],
+    origin: Vec<usize>,
+    twin: Vec<Option<usize>>,
+    /// The index of the constraint each half-edge lies along.
+    constraint: Vec<Option<usize>>,
+    /// Some half-edge leaving each vertex in the mesh.
+    vertex_edge: Vec<usize>,
+    /// The boundary, counter-clockwise: each vertex's neighbours along it and the half-edge
+    /// to the next one.
+    hull_next: Vec<usize>,
+    hull_prev: Vec<usize>,
+    hull_edge: Vec<usize>,
+}
+
+impl<'a, T: Scalar> Builder<'a, T> {
+    fn build(points: &'a [Point<T>], constraints: &[(usize, usize)]) -> Result<Builder<'a, T>, PolyError> {
+        if points.len() < 3 {
+            return Err(PolyError::TooFewVertices { count: points.len() });
+        }
+        if let Some(index) = points.iter().position(|p| !p.x.is_finite() || !p.y.is_finite()) {
+            return Err(PolyError::NonFinite { index });
+        }
+        let n = points.len();
+        if let Some(index) = constraints.iter().position(|&(a, b)| a >= n || b >= n) {
+            return Err(PolyError::InvalidConstraint { index });
+        }
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&i, &j| (points[i].x, points[i].y).partial_cmp(&(points[j].x, points[j].y)).unwrap());
+        // Repeated points stand in for the first of them, which sorts first
+        let mut alias: Vec<usize> = (0..n).collect();
+        let mut distinct: Vec<usize> = Vec::with_capacity(n);
+        for i in order {
+            match distinct.last() {
+                Some(&j) if points[j] == points[i] => alias[i] = j,
+                _ => distinct.push(i),
+            }
+        }
+
+        let mut builder = Builder {
+            points,
+            origin: Vec::new(),
+            twin: Vec::new(),
+            constraint: Vec::new(),
+            vertex_edge: vec![0; n],
+            hull_next: vec![0; n],
+            hull_prev: vec![0; n],
+            hull_edge: vec![0; n],
+        };
+        let started = builder.start(&distinct)?;
+        for k in started..distinct.len() {
+            builder.insert(distinct[k], distinct[k - 1]);
+        }
+        for (id, &(a, b)) in constraints.iter().enumerate() {
+            builder.add_constraint(alias[a], alias[b], id)?;
+        }
+        Ok(builder)
+    }
+
+    fn destination(&self, edge: usize) -> usize {
+        self.origin[next(edge)]
+    }
+
+    fn is_left(&self, a: usize, b: usize, c: usize) -> f64 {
+        is_left(self.points[a], self.points[b], self.points[c])
+    }
+
+    fn add_triangle(&mut self, vertices: [usize; 3]) -> usize {
+        let t = self.origin.len() / 3;
+        self.origin.extend_from_slice(&vertices);
+        self.twin.extend([None; 3]);
+        self.constraint.extend([None; 3]);
+        for (side, &v) in vertices.iter().enumerate() {
+            self.vertex_edge[v] = 3 * t + side;
+        }
+        t
+    }
+
+    fn set_triangle(&mut self, t: usize, vertices: [usize; 3]) {
+        for (side, &v) in vertices.iter().enumerate() {
+            self.origin[3 * t + side] = v;
+            self.vertex_edge[v] = 3 * t + side;
+        }
+    }
+
+    fn link(&mut self, a: usize, b: usize) {
+        self.twin[a] = Some(b);
+        self.twin[b] = Some(a);
+    }
+
+    /// Gives `edge` the neighbour and constraint of an edge that has moved into its place.
+    fn attach(&mut self, edge: usize, (twin, constraint): (Option<usize>, Option<usize>)) {
+        self.twin[edge] = twin;
+        self.constraint[edge] = constraint;
+        match twin {
+            Some(other) => self.twin[other] = Some(edge),
+            None => self.hull_edge[self.origin[edge]] = edge,
+        }
+    }
+
+    /// Every half-edge leaving vertex `a`.
+    fn outgoing(&self, a: usize) -> Vec<usize> {
+        let start = self.vertex_edge[a];
+        let mut edges = vec![start];
+        let mut edge = start;
+        while let Some(turned) = self.twin[prev(edge)] {
+            if turned == start {
+                return edges;
+            }
+            edges.push(turned);
+            edge = turned;
+        }
+        // Turning counter-clockwise ran into the boundary, so turn clockwise from the start too
+        let mut edge = start;
+        while let Some(other) = self.twin[edge] {
+            edge = next(other);
+            edges.push(edge);
+        }
+        edges
+    }
+
+    /// A half-edge between `a` and `b`, running either way, if they are joined.
+    fn find_edge(&self, a: usize, b: usize) -> Option<usize> {
+        let from = |a: usize, b: usize| self.outgoing(a).into_iter().find(|&e| self.destination(e) == b);
+        from(a, b).or_else(|| from(b, a))
+    }
+
+    /// Replaces the edge between the two triangles beside `edge` with the other diagonal of
+    /// the quadrilateral they make. Only the two triangles change.
+    fn flip(&mut self, edge: usize) {
+        let other = self.twin[edge].expect("only interior edges are flipped");
+        let (a, b) = (self.origin[edge], self.origin[other]);
+        let (p0, p1) = (self.origin[prev(edge)], self.origin[prev(other)]);
+        let outer = |e: usize| (self.twin[e], self.constraint[e]);
+        let (a_p1, p1_b, b_p0, p0_a) = (outer(next(other)), outer(prev(other)), outer(next(edge)), outer(prev(edge)));
+        let (t0, t1) = (edge / 3, other / 3);
+        self.set_triangle(t0, [a, p1, p0]);
+        self.set_triangle(t1, [b, p0, p1]);
+        self.attach(3 * t0, a_p1);
+        self.attach(3 * t0 + 2, p0_a);
+        self.attach(3 * t1, b_p0);
+        self.attach(3 * t1 + 2, p1_b);
+        self.link(3 * t0 + 1, 3 * t1 + 1);
+        self.constraint[3 * t0 + 1] = None;
+        self.constraint[3 * t1 + 1] = None;
+    }
+
+    /// Flips the edges between the pairs of vertices in `pending`, and those around them in
+    /// turn, until none has the far vertex of its neighbour inside its circumcircle.
+    /// Constraints are left alone.
+    fn legalize(&mut self, mut pending: Vec<(usize, usize)>) {
+        while let Some((a, b)) = pending.pop() {
+            let Some(edge) = self.find_edge(a, b) else { continue };
+            let Some(other) = self.twin[edge] else { continue };
+            if self.constraint[edge].is_some() {
+                continue;
+            }
+            let (a, b) = (self.origin[edge], self.origin[other]);
+            let (p0, p1) = (self.origin[prev(edge)], self.origin[prev(other)]);
+            if incircle(self.points[a], self.points[b], self.points[p0], self.points[p1]) > 0.0 {
+                self.flip(edge);
+                pending.extend([(a, p1), (p1, b), (b, p0), (p0, a)]);
+            }
+        }
+    }
+
+    /// Triangulates the points of `order` up to the first that is not collinear with those
+    /// before it, as a fan from that point. Returns how many points were used.
+    fn start(&mut self, order: &[usize]) -> Result<usize, PolyError> {
+        let apex = (2..order.len()).find(|&k| self.is_left(order[0], order[1], order[k]) != 0.0).ok_or(PolyError::Degenerate)?;
+        let counter_clockwise = self.is_left(order[0], order[1], order[apex]) > 0.0;
+        let tip = order[apex];
+        for i in 0..apex - 1 {
+            let (a, b) = (order[i], order[i + 1]);
+            let t = self.add_triangle(if counter_clockwise { [a, b, tip] } else { [b, a, tip] });
+            if i > 0 {
+                // Triangle t shares the spoke to `a` with the triangle before it
+                if counter_clockwise {
+                    self.link(3 * t - 2, 3 * t + 2);
+                } else {
+                    self.link(3 * t - 1, 3 * t + 1);
+                }
+            }
+        }
+        for edge in 0..self.origin.len() {
+            if self.twin[edge].is_none() {
+                let (a, b) = (self.origin[edge], self.destination(edge));
+                self.hull_next[a] = b;
+                self.hull_prev[b] = a;
+                self.hull_edge[a] = edge;
+            }
+        }
+        self.legalize((1..apex - 1).map(|i| (order[i], tip)).collect());
+        Ok(apex + 1)
+    }
+
+    /// Adds the point `p`, which lies outside the triangulation, beyond `last`, the point
+    /// added before it.
+    fn insert(&mut self, p: usize, last: usize) {
+        // The hull edges `p` can see run on from `last` in one or both directions
+        let visible = |builder: &Self, a: usize, b: usize| builder.is_left(a, b, p) < 0.0;
+        let mut first = last;
+        while visible(self, self.hull_prev[first], first) {
+            first = self.hull_prev[first];
+        }
+        let mut end = last;
+        while visible(self, end, self.hull_next[end]) {
+            end = self.hull_next[end];
+        }
+
+        let mut covered = Vec::new();
+        let (mut first_spoke, mut last_spoke) = (None, None);
+        let mut v = first;
+        while v != end {
+            let (w, hull_edge) = (self.hull_next[v], self.hull_edge[v]);
+            let t = self.add_triangle([w, v, p]);
+            self.link(3 * t, hull_edge);
+            match last_spoke {
+                Some(spoke) => self.link(3 * t + 1, spoke),
+                None => first_spoke = Some(3 * t + 1),
+            }
+            last_spoke = Some(3 * t + 2);
+            covered.push((v, w));
+            v = w;
+        }
+        self.hull_next[first] = p;
+        self.hull_prev[p] = first;
+        self.hull_next[p] = end;
+        self.hull_prev[end] = p;
+        self.hull_edge[first] = first_spoke.expect("a point beyond the hull sees some edge");
+        self.hull_edge[p] = last_spoke.expect("a point beyond the hull sees some edge");
+        self.legalize(covered);
+    }
+
+    /// Makes the segment from `u` to `v` an edge, marked as constraint `id`.
+    fn add_constraint(&mut self, u: usize, v: usize, id: usize) -> Result<(), PolyError> {
+        let mut pending = vec![(u, v)];
+        'pieces: while let Some((u, v)) = pending.pop() {
+            if u == v {
+                continue;
+            }
+            if let Some(edge) = self.find_edge(u, v) {
+                self.mark(edge, id);
+                continue;
+            }
+            let (pu, pv) = (self.points[u], self.points[v]);
+            let ahead = |w: usize| {
+                let pw = self.points[w];
+                is_left(pu, pv, pw) == 0.0 && (pw.x - pu.x) * (pv.x - pu.x) + (pw.y - pu.y) * (pv.y - pu.y) > T::ZERO
+            };
+            // Find the triangle around `u` the segment leaves through, or a vertex on the way
+            let mut entry = None;
+            for edge in self.outgoing(u) {
+                let (x, y) = (self.destination(edge), self.origin[prev(edge)]);
+                if let Some(w) = [x, y].into_iter().find(|&w| ahead(w)) {
+                    pending.extend([(w, v), (u, w)]);
+                    continue 'pieces;
+                }
+                if self.is_left(u, x, v) > 0.0 && self.is_left(u, y, v) < 0.0 {
+                    entry = Some(next(edge));
+                }
+            }
+            let mut edge = entry.expect("a segment between two vertices leaves one of the triangles around its start");
+
+            // Walk along the segment collecting the edges it crosses, stopping early at a vertex on it
+            let mut crossed = VecDeque::new();
+            let mut end = v;
+            loop {
+                if let Some(first) = self.constraint[edge] {
+                    return Err(PolyError::CrossingConstraints { first, second: id });
+                }
+                crossed.push_back((self.origin[edge], self.destination(edge)));
+                let other = self.twin[edge].expect("a segment inside the hull only crosses interior edges");
+                let w = self.origin[prev(other)];
+                if w == v {
+                    break;
+                }
+                let side = self.is_left(u, v, w);
+                if side == 0.0 {
+                    pending.push((w, v));
+                    end = w;
+                    break;
+                }
+                // Cross next the edge whose ends lie on opposite sides
+                edge = if (side > 0.0) == (self.is_left(u, v, self.origin[other]) > 0.0) { next(other) } else { prev(other) };
+            }
+
+            // Flip the crossed edges out of the way, setting aside those that cannot be flipped yet
+            let mut created = Vec::new();
+            while let Some((a, b)) = crossed.pop_front() {
+                let edge = self.find_edge(a, b).expect("crossed edges are still in the mesh");
+                let other = self.twin[edge].expect("crossed edges are interior");
+                let (a, b) = (self.origin[edge], self.origin[other]);
+                let (p0, p1) = (self.origin[prev(edge)], self.origin[prev(other)]);
+                if !strictly_opposite(self.is_left(p0, p1, a), self.is_left(p0, p1, b)) {
+                    crossed.push_back((a, b));
+                    continue;
+                }
+                self.flip(edge);
+                if strictly_opposite(self.is_left(u, end, p0), self.is_left(u, end, p1)) && strictly_opposite(self.is_left(p0, p1, u), self.is_left(p0, p1, end)) {
+                    crossed.push_back((p0, p1));
+                } else {
+                    created.push((p0, p1));
+                }
+            }
+            let edge = self.find_edge(u, end).expect("flipping away the crossed edges joins the ends");
+            self.mark(edge, id);
+            self.legalize(created);
+        }
+        Ok(())
+    }
+
+    fn mark(&mut self, edge: usize, id: usize) {
+        self.constraint[edge] = Some(id);
+        if let Some(other) = self.twin[edge] {
+            self.constraint[other] = Some(id);
+        }
+    }
+
+    /// Which triangles are enclosed by the constraints: those reached from outside the hull
+    /// by crossing an odd number of them.
+    fn inside_constraints(&self) -> Vec<bool> {
+        let count = self.origin.len() / 3;
+        let mut depth = vec![None; count];
+        // Breadth first by the number of constraints crossed, which is always 0 or 1 more
+        // than the triangle being left
+        let mut queue = VecDeque::new();
+        for edge in (0..self.origin.len()).filter(|&e| self.twin[e].is_none()) {
+            if self.constraint[edge].is_some() {
+                queue.push_back((edge / 3, 1));
+            } else {
+                queue.push_front((edge / 3, 0));
+            }
+        }
+        while let Some((t, d)) = queue.pop_front() {
+            if depth[t].is_some() {
+                continue;
+            }
+            depth[t] = Some(d);
+            for edge in 3 * t..3 * t + 3 {
+                match self.twin[edge] {
+                    Some(other) if depth[other / 3].is_none() => {
+                        if self.constraint[edge].is_some() {
+                            queue.push_back((other / 3, d + 1));
+                        } else {
+                            queue.push_front((other / 3, d));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        depth.into_iter().map(|d| d.is_some_and(|d| d % 2 == 1)).collect()
+    }
+
+    /// The mesh of the triangles marked in `keep`, with neighbours that were dropped left out.
+    fn into_mesh(self, keep: &[bool]) -> Mesh<T> {
+        let mut renumbered = vec![None; keep.len()];
+        for (kept, t) in (0..keep.len()).filter(|&t| keep[t]).enumerate() {
+            renumbered[t] = Some(kept);
+        }
+        let half_edges = (0..self.origin.len())
+            .filter(|&e| keep[e / 3])
+            .map(|e| HalfEdge {
+                origin: self.origin[e],
+                twin: self.twin[e].and_then(|other| renumbered[other / 3].map(|t| 3 * t + other % 3)),
+                constrained: self.constraint[e].is_some(),
+            })
+            .collect();
+        Mesh { vertices: self.points.to_vec(), half_edges }
+    }
+}
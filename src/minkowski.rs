@@ -0,0 +1,77 @@
+//! Minkowski sums and differences of convex polygons.
+//!
+//! The edges of the sum of two convex polygons are the edges of both, sorted by
+//! direction. Walking both counter-clockwise boundaries from their extreme vertex
+//! and always taking the edge that turns least gives the sum in O(n + m).
+
+use std::cmp::Ordering;
+
+use crate::convex::ConvexPolygon;
+use crate::polygon::leftmost_lowest;
+use crate::predicates::cross_sign;
+use crate::primitives::Point;
+use crate::scalar::Scalar;
+
+/// Whether the direction `p0` → `p1` points right or straight up, rather than left or
+/// straight down. Within each half, directions compare exactly by the sign of their cross product.
+fn right_half<T: Scalar>(p0: Point<T>, p1: Point<T>) -> bool {
+    p1.x > p0.x || (p1.x == p0.x && p1.y > p0.y)
+}
+
+/// How the direction of edge `a0` → `a1` compares with that of `b0` → `b1`, with angles
+/// measured counter-clockwise from straight down.
+fn compare_directions<T: Scalar>(a0: Point<T>, a1: Point<T>, b0: Point<T>, b1: Point<T>) -> Ordering {
+    match (right_half(a0, a1), right_half(b0, b1)) {
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        _ => 0.0.partial_cmp(&cross_sign(a0, a1, b0, b1)).unwrap(),
+    }
+}
+
+impl<T: Scalar> ConvexPolygon<T> {
+    /// The set of sums `p + q` of a point `p` of this polygon and a point `q` of `other`.
+    ///
+    /// Runs in O(n + m) by merging the edges of both polygons, whose vertices must run
+    /// counter-clockwise as `new` leaves them. The result starts at its leftmost-lowest
+    /// vertex, and edges of the two polygons that point the same way are merged into one.
+    pub fn minkowski_sum(&self, other: &ConvexPolygon<T>) -> ConvexPolygon<T> {
+        let (a, b) = (&self.vertices, &other.vertices);
+        let (n, m) = (a.len(), b.len());
+        if n == 0 || m == 0 {
+            return ConvexPolygon { vertices: Vec::new() };
+        }
+        let (start_a, start_b) = (leftmost_lowest(a), leftmost_lowest(b));
+        let vertex_a = |i: usize| a[(start_a + i) % n];
+        let vertex_b = |j: usize| b[(start_b + j) % m];
+
+        let mut vertices = Vec::with_capacity(n + m);
+        let (mut i, mut j) = (0, 0);
+        while i < n || j < m {
+            let (p, q) = (vertex_a(i), vertex_b(j));
+            vertices.push(Point::new(p.x + q.x, p.y + q.y));
+            let order = if i == n {
+                Ordering::Greater
+            } else if j == m {
+                Ordering::Less
+            } else {
+                compare_directions(p, vertex_a(i + 1), q, vertex_b(j + 1))
+            };
+            if order != Ordering::Greater {
+                i += 1;
+            }
+            if order != Ordering::Less {
+                j += 1;
+            }
+        }
+        ConvexPolygon { vertices }
+    }
+
+    /// The set of differences `p - q` of a point `p` of this polygon and a point `q` of
+    /// `other`, which holds the origin exactly when the polygons overlap. Its distance from the
+    /// origin is the distance between them.
+    pub fn minkowski_difference(&self, other: &ConvexPolygon<T>) -> ConvexPolygon<T> {
+        // Negating every vertex turns the polygon half way round, so it still runs counter-clockwise.
+        let negated = ConvexPolygon { vertices: other.vertices.iter().map(|p| Point::new(-p.x, -p.y)).collect() };
+        self.minkowski_sum(&negated)
+    }
+}
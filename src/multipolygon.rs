@@ -0,0 +1,210 @@
+use crate::aabb::Aabb;
+use crate::error::PolyError;
+use crate::polygon::{near_ring, ring_contains, ring_twice_area, rings_cross, Polygon};
+use crate::primitives::{is_left, Point, Segment, SegmentIntersection};
+use crate::scalar::Scalar;
+use crate::tolerance::Tolerance;
+
+/// A polygon with zero or more holes. The exterior ring runs counter-clockwise
+/// and each interior ring runs clockwise.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PolygonWithHoles<T> {
+    pub exterior: Polygon<T>,
+    pub interiors: Vec<Polygon<T>>,
+}
+
+/// Any number of polygons with holes whose interiors do not overlap.
+#[derive(Debug, PartialEq, Clone)]
+pub struct MultiPolygon<T> {
+    pub polygons: Vec<PolygonWithHoles<T>>,
+}
+
+/// The corners of `bounds`, or `None` if it is empty.
+fn corners<T: Scalar>(bounds: Aabb<T>) -> Option<(Point<T>, Point<T>)> {
+    (!bounds.is_empty()).then_some((bounds.min, bounds.max))
+}
+
+fn oriented<T: Scalar>(ring: Polygon<T>, counter_clockwise: bool) -> Polygon<T> {
+    let mut vertices = ring.vertices;
+    if (ring_twice_area(&vertices) > T::ZERO) != counter_clockwise {
+        vertices.reverse();
+    }
+    Polygon { vertices }
+}
+
+/// Whether `p` is strictly inside `ring`, off its boundary and not within `tolerance` of a vertex.
+fn strictly_inside<T: Scalar>(ring: &[Point<T>], p: Point<T>, tolerance: &Tolerance<T>) -> bool {
+    ring_contains(ring, p, tolerance) && !near_ring(ring, p, tolerance)
+}
+
+/// A point strictly inside `ring`: the centroid of its largest triangle.
+fn interior_point<T: Scalar>(ring: &Polygon<T>) -> Option<Point<T>> {
+    let area = |t: &[usize; 3]| is_left(ring.vertices[t[0]], ring.vertices[t[1]], ring.vertices[t[2]]);
+    let largest = ring.triangulate().into_iter().reduce(|best, t| if area(&t) > area(&best) { t } else { best })?;
+    let [a, b, c] = largest.map(|i| ring.vertices[i]);
+    let three = T::from_f64(3.0);
+    Some(Point::new((a.x + b.x + c.x) / three, (a.y + b.y + c.y) / three))
+}
+
+/// Whether an edge of ring `a` runs along an edge of ring `b` for some length.
+fn rings_share_edge<T: Scalar>(a: &[Point<T>], b: &[Point<T>], tolerance: &Tolerance<T>) -> bool {
+    let (n, m) = (a.len(), b.len());
+    (0..n).any(|i| {
+        let edge_a = Segment::new(a[i], a[(i + 1) % n]);
+        (0..m).any(|j| matches!(edge_a.intersection_with_tolerance(&Segment::new(b[j], b[(j + 1) % m]), tolerance), SegmentIntersection::Overlap(_)))
+    })
+}
+
+/// Whether the holes `one` and `two` share any area or any stretch of boundary.
+fn holes_overlap<T: Scalar>(one: &Polygon<T>, two: &Polygon<T>, tolerance: &Tolerance<T>) -> bool {
+    // Holes whose vertices all lie on each other's boundary, duplicates among them, are
+    // caught by a point from inside one
+    let inside_other = |a: &Polygon<T>, b: &Polygon<T>| interior_point(a).is_some_and(|p| ring_contains(&b.vertices, p, tolerance));
+    rings_cross(&one.vertices, &two.vertices)
+        || two.vertices.iter().any(|v| strictly_inside(&one.vertices, *v, tolerance))
+        || one.vertices.iter().any(|v| strictly_inside(&two.vertices, *v, tolerance))
+        || inside_other(one, two)
+        || inside_other(two, one)
+        || rings_share_edge(&one.vertices, &two.vertices, tolerance)
+}
+
+impl<T: Scalar> PolygonWithHoles<T> {
+    /// Builds a polygon with holes, reversing rings as needed so the exterior runs
+    /// counter-clockwise and the holes clockwise.
+    ///
+    /// Panics if a hole is not inside the exterior or two holes overlap; see `try_new`.
+    pub fn new(exterior: Polygon<T>, interiors: Vec<Polygon<T>>) -> PolygonWithHoles<T> {
+        PolygonWithHoles::try_new(exterior, interiors).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Builds a polygon with holes, returning an error instead of panicking when a
+    /// hole is not inside the exterior or two holes overlap.
+    ///
+    /// Holes may touch the exterior and each other at points, within `Tolerance::default()`,
+    /// but not share an edge.
+    pub fn try_new(exterior: Polygon<T>, interiors: Vec<Polygon<T>>) -> Result<PolygonWithHoles<T>, PolyError> {
+        PolygonWithHoles::try_new_with_tolerance(exterior, interiors, &Tolerance::default())
+    }
+
+    /// Like `try_new`, treating vertices within `tolerance` of each other as touching.
+    pub fn try_new_with_tolerance(exterior: Polygon<T>, interiors: Vec<Polygon<T>>, tolerance: &Tolerance<T>) -> Result<PolygonWithHoles<T>, PolyError> {
+        for (index, hole) in interiors.iter().enumerate() {
+            // Checking both rings' vertices catches a hole covering a notch in the exterior
+            if rings_cross(&exterior.vertices, &hole.vertices)
+                || hole.vertices.iter().any(|v| !ring_contains(&exterior.vertices, *v, tolerance))
+                || exterior.vertices.iter().any(|v| strictly_inside(&hole.vertices, *v, tolerance))
+                || interior_point(hole).is_some_and(|p| !ring_contains(&exterior.vertices, p, tolerance))
+                || rings_share_edge(&exterior.vertices, &hole.vertices, tolerance)
+            {
+                return Err(PolyError::HoleOutsideExterior { index });
+            }
+        }
+        for (first, one) in interiors.iter().enumerate() {
+            for (second, two) in interiors.iter().enumerate().skip(first + 1) {
+                if holes_overlap(one, two, tolerance) {
+                    return Err(PolyError::OverlappingHoles { first, second });
+                }
+            }
+        }
+        Ok(PolygonWithHoles {
+            exterior: oriented(exterior, true),
+            interiors: interiors.into_iter().map(|hole| oriented(hole, false)).collect(),
+        })
+    }
+
+    /// Converts the vertices to another scalar type.
+    ///
+    /// Narrowing conversions round each coordinate, so the result is not re-validated.
+    pub fn cast<U: Scalar>(&self) -> PolygonWithHoles<U> {
+        PolygonWithHoles { exterior: self.exterior.cast(), interiors: self.interiors.iter().map(|hole| hole.cast()).collect() }
+    }
+
+    /// The area of the exterior less the area of the holes.
+    pub fn area(&self) -> T {
+        self.exterior.area() - self.interiors.iter().map(|hole| hole.area()).sum::<T>()
+    }
+
+    /// The smallest and largest coordinates of the exterior, in the form the SVG
+    /// functions take as a view box, or `None` if it has no vertices.
+    #[deprecated(note = "use `bounds`, which returns an `Aabb` like every other shape")]
+    pub fn bounding_box(&self) -> Option<(Point<T>, Point<T>)> {
+        corners(self.bounds())
+    }
+
+    /// The bounding box of the exterior.
+    pub fn bounds(&self) -> Aabb<T> {
+        self.exterior.bounds()
+    }
+
+    /// Whether `p` is inside the polygon or on its boundary, within `Tolerance::default()`.
+    /// Points on the edge of a hole count as inside.
+    pub fn is_point_inside(&self, p: Point<T>) -> bool {
+        self.is_point_inside_with_tolerance(p, &Tolerance::default())
+    }
+
+    /// Whether `p` is inside the polygon, on its boundary or within `tolerance` of a vertex.
+    pub fn is_point_inside_with_tolerance(&self, p: Point<T>, tolerance: &Tolerance<T>) -> bool {
+        ring_contains(&self.exterior.vertices, p, tolerance) && !self.interiors.iter().any(|hole| strictly_inside(&hole.vertices, p, tolerance))
+    }
+}
+
+impl<T: Scalar> From<Polygon<T>> for PolygonWithHoles<T> {
+    fn from(polygon: Polygon<T>) -> PolygonWithHoles<T> {
+        PolygonWithHoles { exterior: oriented(polygon, true), interiors: Vec::new() }
+    }
+}
+
+impl From<PolygonWithHoles<f32>> for PolygonWithHoles<f64> {
+    fn from(polygon: PolygonWithHoles<f32>) -> PolygonWithHoles<f64> {
+        polygon.cast()
+    }
+}
+
+impl<T: Scalar> MultiPolygon<T> {
+    pub fn is_empty(&self) -> bool {
+        self.polygons.is_empty()
+    }
+
+    /// Converts the vertices to another scalar type.
+    pub fn cast<U: Scalar>(&self) -> MultiPolygon<U> {
+        MultiPolygon { polygons: self.polygons.iter().map(|polygon| polygon.cast()).collect() }
+    }
+
+    /// The total area of all the parts.
+    pub fn area(&self) -> T {
+        self.polygons.iter().map(|polygon| polygon.area()).sum()
+    }
+
+    /// The smallest and largest coordinates of all the parts, or `None` if there are no parts.
+    #[deprecated(note = "use `bounds`, which returns an `Aabb` like every other shape")]
+    pub fn bounding_box(&self) -> Option<(Point<T>, Point<T>)> {
+        corners(self.bounds())
+    }
+
+    /// The bounding box of all the parts, which is empty if there are no parts.
+    pub fn bounds(&self) -> Aabb<T> {
+        self.polygons.iter().fold(Aabb::empty(), |bounds, polygon| bounds.union(&polygon.bounds()))
+    }
+
+    /// Whether `p` is inside any part or on its boundary, within `Tolerance::default()`.
+    pub fn is_point_inside(&self, p: Point<T>) -> bool {
+        self.is_point_inside_with_tolerance(p, &Tolerance::default())
+    }
+
+    /// Whether `p` is inside any part, on its boundary or within `tolerance` of a vertex.
+    pub fn is_point_inside_with_tolerance(&self, p: Point<T>, tolerance: &Tolerance<T>) -> bool {
+        self.polygons.iter().any(|polygon| polygon.is_point_inside_with_tolerance(p, tolerance))
+    }
+}
+
+impl<T: Scalar> From<PolygonWithHoles<T>> for MultiPolygon<T> {
+    fn from(polygon: PolygonWithHoles<T>) -> MultiPolygon<T> {
+        MultiPolygon { polygons: vec![polygon] }
+    }
+}
+
+impl From<MultiPolygon<f32>> for MultiPolygon<f64> {
+    fn from(multi: MultiPolygon<f32>) -> MultiPolygon<f64> {
+        multi.cast()
+    }
+}
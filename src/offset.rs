@@ -0,0 +1,156 @@
+//! Offsetting polygons outwards or inwards by a distance.
+//!
+//! Each edge moves out along its normal. Where neighbouring edges move apart, at
+//! corners pointing away from the direction of the offset, the gap between them
+//! is filled according to the `JoinStyle`. Convex polygons are offset directly.
+//! Simple polygons are offset by sweeping every edge into a strip and taking the
+//! union of the polygon and the strips, or for inward offsets removing the strips
+//! from it, which deals with edges that run into each other.
+
+use crate::boolean::{counter_clockwise, subtract_rings, union_rings};
+use crate::clip::clip_ring_to_half_plane;
+use crate::convex::ConvexPolygon;
+use crate::multipolygon::MultiPolygon;
+use crate::polygon::{ring_twice_area, Polygon};
+use crate::primitives::{is_left, outward_normal, Point};
+use crate::scalar::Scalar;
+use crate::tolerance::Tolerance;
+
+/// How an offset fills the gap at a corner where the moved edges no longer meet.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum JoinStyle {
+    /// Extend both edges until they meet in a point. Sharp corners give long spikes.
+    Miter,
+    /// Follow a circular arc around the corner, approximated with straight segments at the
+    /// rate of `segments` to a full circle. Each corner gets at least one.
+    Round { segments: usize },
+    /// Cut the corner off with a single straight edge.
+    Bevel,
+}
+
+/// Each edge of `ring` moved by `distance` along its outward normal.
+fn moved_edges<T: Scalar>(ring: &[Point<T>], distance: T) -> Vec<(Point<T>, Point<T>)> {
+    let n = ring.len();
+    (0..n)
+        .map(|i| {
+            let (a, b) = (ring[i], ring[(i + 1) % n]);
+            let normal = outward_normal(a, b);
+            let shift = |p: Point<T>| Point::new(p.x + distance * normal.x, p.y + distance * normal.y);
+            (shift(a), shift(b))
+        })
+        .collect()
+}
+
+/// The points filling the corner at `vertex` from `from` round to `to`, which are both the
+/// offset distance from it, with `from` and `to` included.
+fn join<T: Scalar>(vertex: Point<T>, from: Point<T>, to: Point<T>, style: JoinStyle) -> Vec<Point<T>> {
+    let (u, v) = (Point::new(from.x - vertex.x, from.y - vertex.y), Point::new(to.x - vertex.x, to.y - vertex.y));
+    let radius_squared = u.x * u.x + u.y * u.y;
+    let (cross, dot) = (u.x * v.y - u.y * v.x, u.x * v.x + u.y * v.y);
+    match style {
+        JoinStyle::Bevel => vec![from, to],
+        JoinStyle::Miter => {
+            // The tip lies along the bisector, at radius / cos(half the angle) from the vertex
+            let scale = radius_squared / (radius_squared + dot);
+            vec![from, Point::new(vertex.x + (u.x + v.x) * scale, vertex.y + (u.y + v.y) * scale), to]
+        }
+        JoinStyle::Round { segments } => {
+            let angle = cross.atan2(dot);
+            let count = (angle.abs().to_f64() / (2.0 * std::f64::consts::PI) * segments as f64).ceil().max(1.0) as usize;
+            let start = u.y.atan2(u.x);
+            let radius = radius_squared.sqrt();
+            let mut points = vec![from];
+            for k in 1..count {
+                let theta = start + angle * T::from_usize(k) / T::from_usize(count);
+                points.push(Point::new(vertex.x + radius * theta.cos(), vertex.y + radius * theta.sin()));
+            }
+            points.push(to);
+            points
+        }
+    }
+}
+
+impl<T: Scalar> ConvexPolygon<T> {
+    /// The polygon grown by `distance`, or shrunk if it is negative, with corners filled in
+    /// by `style` when growing. Returns `None` if shrinking leaves nothing.
+    ///
+    /// Shrinking keeps the corners sharp whatever the style, as the corners of a convex
+    /// polygon all point outwards. The result runs counter-clockwise.
+    pub fn offset(&self, distance: T, style: JoinStyle) -> Option<ConvexPolygon<T>> {
+        let ring = counter_clockwise(&self.vertices);
+        let n = ring.len();
+        if n < 3 {
+            return None;
+        }
+        let edges = moved_edges(&ring, distance);
+        let vertices = if distance >= T::ZERO {
+            let mut vertices = Vec::new();
+            for i in 0..n {
+                let (from, to) = (edges[(i + n - 1) % n].1, edges[i].0);
+                if from == to {
+                    vertices.push(from);
+                } else if style == JoinStyle::Miter {
+                    // Only the tip; the ends of the moved edges lie on the lines to it
+                    vertices.push(join(ring[i], from, to, style)[1]);
+                } else {
+                    vertices.extend(join(ring[i], from, to, style));
+                }
+            }
+            vertices.dedup();
+            vertices
+        } else {
+            // The polygon is the intersection of the half-planes left of its edges; move them in.
+            edges.iter().fold(ring.clone(), |clipped, (a, b)| clip_ring_to_half_plane(&clipped, *a, *b, 1.0))
+        };
+        if vertices.len() < 3 || ring_twice_area(&vertices) <= T::ZERO {
+            return None;
+        }
+        Some(ConvexPolygon { vertices })
+    }
+}
+
+impl<T: Scalar> Polygon<T> {
+    /// The polygon grown by `distance`, or shrunk if it is negative, with corners filled in
+    /// by `style` where the moved edges part.
+    ///
+    /// Growing can close up bays into holes and shrinking can split the polygon into pieces
+    /// or leave nothing, so the result is a `MultiPolygon`. Uses `Tolerance::default()`; see
+    /// `offset_with_tolerance`.
+    pub fn offset(&self, distance: T, style: JoinStyle) -> MultiPolygon<T> {
+        self.offset_with_tolerance(distance, style, &Tolerance::default())
+    }
+
+    /// Like `offset`, treating points within `tolerance` of each other as the same when
+    /// combining the swept edges.
+    ///
+    /// Every swept edge is tested against every other, so this takes O(n²) time.
+    pub fn offset_with_tolerance(&self, distance: T, style: JoinStyle, tolerance: &Tolerance<T>) -> MultiPolygon<T> {
+        let ring = counter_clockwise(&self.vertices);
+        let n = ring.len();
+        if n < 3 {
+            return MultiPolygon { polygons: Vec::new() };
+        }
+        if distance == T::ZERO {
+            return union_rings(&[ring], tolerance);
+        }
+        let edges = moved_edges(&ring, distance);
+        let mut strips: Vec<Vec<Point<T>>> = (0..n).map(|i| vec![ring[i], ring[(i + 1) % n], edges[i].1, edges[i].0]).collect();
+        // Corners turning away from the offset need filling: convex ones when growing, reflex
+        // ones when shrinking.
+        let away = if distance > T::ZERO { 1.0 } else { -1.0 };
+        for i in 0..n {
+            let (previous, vertex, next) = (ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]);
+            if away * is_left(previous, vertex, next) > 0.0 {
+                let mut corner = vec![vertex];
+                corner.extend(join(vertex, edges[(i + n - 1) % n].1, edges[i].0, style));
+                strips.push(corner);
+            }
+        }
+        if distance > T::ZERO {
+            strips.push(ring);
+            union_rings(&strips, tolerance)
+        } else {
+            subtract_rings(&ring, &strips, tolerance)
+        }
+    }
+}
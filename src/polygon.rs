@@ -0,0 +1,244 @@
+use crate::aabb::{bounds_may_touch, Aabb};
+use crate::error::PolyError;
+use crate::primitives::{is_left, near_segment, Point, Segment, SegmentIntersection};
+use crate::scalar::Scalar;
+use crate::tolerance::Tolerance;
+
+/// A simple polygon: a closed boundary that may be concave but never touches
+/// or crosses itself. Vertices keep the order they were given in, which may be
+/// clockwise or counter-clockwise.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Polygon<T> {
+    pub vertices: Vec<Point<T>>,
+}
+
+/// Whether `p` is inside the ring `vertices`, on its boundary or within `tolerance` of
+/// a vertex. Works for either vertex order.
+pub(crate) fn ring_contains<T: Scalar>(vertices: &[Point<T>], p: Point<T>, tolerance: &Tolerance<T>) -> bool {
+    near_ring(vertices, p, tolerance) || winding_number(vertices, p) != 0
+}
+
+/// How many times the ring winds counter-clockwise around `p`, which must not be on it.
+pub(crate) fn winding_number<T: Scalar>(vertices: &[Point<T>], p: Point<T>) -> i32 {
+    let mut winding_number = 0;
+    let n = vertices.len();
+    for i in 0..n {
+        let v1 = vertices[i];
+        let v2 = vertices[(i + 1) % n];
+        if v1.y <= p.y {
+            if v2.y > p.y && is_left(v1, v2, p) > 0.0 {
+                winding_number += 1;
+            }
+        } else if v2.y <= p.y && is_left(v1, v2, p) < 0.0 {
+            winding_number -= 1;
+        }
+    }
+    winding_number
+}
+
+/// Whether `p` is on the boundary of the ring `vertices`, or within `tolerance` of one of its vertices.
+pub(crate) fn near_ring<T: Scalar>(vertices: &[Point<T>], p: Point<T>, tolerance: &Tolerance<T>) -> bool {
+    let n = vertices.len();
+    (0..n).any(|i| near_segment(p, &Segment::new(vertices[i], vertices[(i + 1) % n]), tolerance))
+}
+
+/// Whether an edge of ring `a` properly crosses an edge of ring `b`, each passing
+/// strictly from one side of the other. Edges that only touch do not count.
+pub(crate) fn rings_cross<T: Scalar>(a: &[Point<T>], b: &[Point<T>]) -> bool {
+    let (n, m) = (a.len(), b.len());
+    (0..n).any(|i| {
+        let (a1, a2) = (a[i], a[(i + 1) % n]);
+        (0..m).any(|j| {
+            let (b1, b2) = (b[j], b[(j + 1) % m]);
+            is_left(a1, a2, b1) * is_left(a1, a2, b2) < 0.0 && is_left(b1, b2, a1) * is_left(b1, b2, a2) < 0.0
+        })
+    })
+}
+
+/// The index of the leftmost vertex, the lowest of them if several share the smallest `x`,
+/// or 0 if there are none.
+pub(crate) fn leftmost_lowest<T: Scalar>(vertices: &[Point<T>]) -> usize {
+    (0..vertices.len())
+        .min_by(|&i, &j| (vertices[i].x, vertices[i].y).partial_cmp(&(vertices[j].x, vertices[j].y)).unwrap())
+        .unwrap_or(0)
+}
+
+/// The total length of the ring's edges, including the one closing it.
+pub(crate) fn ring_perimeter<T: Scalar>(vertices: &[Point<T>]) -> T {
+    let n = vertices.len();
+    (0..n).map(|i| vertices[i].distance(vertices[(i + 1) % n])).sum()
+}
+
+/// Twice the signed area of the ring: positive when it runs counter-clockwise.
+pub(crate) fn ring_twice_area<T: Scalar>(vertices: &[Point<T>]) -> T {
+    let n = vertices.len();
+    if n == 0 {
+        return T::ZERO;
+    }
+    // Measured relative to the first vertex to keep precision far from the origin.
+    let origin = vertices[0];
+    (0..n)
+        .map(|i| {
+            let (a, b) = (vertices[i], vertices[(i + 1) % n]);
+            (a.x - origin.x) * (b.y - origin.y) - (b.x - origin.x) * (a.y - origin.y)
+        })
+        .sum()
+}
+
+/// Drops vertices equal to the one before them, including the last if it repeats the first.
+pub(crate) fn dedup_consecutive<T: Scalar>(vertices: &[Point<T>], tolerance: &Tolerance<T>) -> Vec<Point<T>> {
+    dedup_consecutive_indices(vertices, tolerance).into_iter().map(|i| vertices[i]).collect()
+}
+
+/// The indices of the vertices `dedup_consecutive` keeps, in order.
+pub(crate) fn dedup_consecutive_indices<T: Scalar>(vertices: &[Point<T>], tolerance: &Tolerance<T>) -> Vec<usize> {
+    let mut kept: Vec<usize> = Vec::with_capacity(vertices.len());
+    for (i, p) in vertices.iter().enumerate() {
+        if kept.last().is_none_or(|&last| !tolerance.points_eq(vertices[last], *p)) {
+            kept.push(i);
+        }
+    }
+    while kept.len() > 1 && tolerance.points_eq(vertices[kept[0]], vertices[kept[kept.len() - 1]]) {
+        kept.pop();
+    }
+    kept
+}
+
+/// Whether two edges that share no vertex touch anywhere, including by overlapping along a line.
+fn edges_touch<T: Scalar>(e1: &Segment<T>, e2: &Segment<T>, tolerance: &Tolerance<T>) -> bool {
+    if near_segment(e1.p1, e2, tolerance) || near_segment(e1.p2, e2, tolerance) || near_segment(e2.p1, e1, tolerance) || near_segment(e2.p2, e1, tolerance) {
+        return true;
+    }
+    let o1 = is_left(e1.p1, e1.p2, e2.p1);
+    let o2 = is_left(e1.p1, e1.p2, e2.p2);
+    let o3 = is_left(e2.p1, e2.p2, e1.p1);
+    let o4 = is_left(e2.p1, e2.p2, e1.p2);
+    (o1 > 0.0) != (o2 > 0.0) && (o3 > 0.0) != (o4 > 0.0)
+}
+
+/// Checks that no two edges of the ring meet except adjacent edges at their shared vertex.
+fn validate_simple<T: Scalar>(vertices: &[Point<T>], tolerance: &Tolerance<T>) -> Result<(), PolyError> {
+    let n = vertices.len();
+    let edge = |i: usize| Segment::new(vertices[i], vertices[(i + 1) % n]);
+    for i in 0..n {
+        // The next edge may only meet this one at their shared vertex, not fold back along it.
+        let (this, next) = (edge(i), edge((i + 1) % n));
+        if near_segment(this.p1, &next, tolerance) || near_segment(next.p2, &this, tolerance) {
+            return Err(PolyError::SelfIntersecting { first: i, second: (i + 1) % n });
+        }
+        for j in i + 2..n {
+            if i == 0 && j == n - 1 {
+                continue;
+            }
+            if edges_touch(&this, &edge(j), tolerance) {
+                return Err(PolyError::SelfIntersecting { first: i, second: j });
+            }
+        }
+    }
+    Ok(())
+}
+
+impl<T: Scalar> Polygon<T> {
+    /// Builds a simple polygon, keeping the vertices in the order given.
+    ///
+    /// Panics if the vertices do not form a simple polygon; see `try_new`.
+    pub fn new(vertices: &[Point<T>]) -> Polygon<T> {
+        Polygon::try_new(vertices).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Builds a simple polygon, keeping the vertices in the order given, and
+    /// returning an error instead of panicking when the input is invalid.
+    ///
+    /// Consecutive vertices equal within `Tolerance::default()` are merged.
+    pub fn try_new(vertices: &[Point<T>]) -> Result<Polygon<T>, PolyError> {
+        Polygon::try_new_with_tolerance(vertices, &Tolerance::default())
+    }
+
+    /// Like `try_new`, merging consecutive vertices that are equal within `tolerance`
+    /// and treating a vertex within `tolerance` of another edge's end as touching it.
+    ///
+    /// Every pair of edges is checked, so validation takes O(n²) time.
+    pub fn try_new_with_tolerance(vertices: &[Point<T>], tolerance: &Tolerance<T>) -> Result<Polygon<T>, PolyError> {
+        if vertices.len() < 3 {
+            return Err(PolyError::TooFewVertices { count: vertices.len() });
+        }
+        if let Some(index) = vertices.iter().position(|p| !p.x.is_finite() || !p.y.is_finite()) {
+            return Err(PolyError::NonFinite { index });
+        }
+        let vertices = dedup_consecutive(vertices, tolerance);
+        if vertices.len() < 3 || vertices.iter().all(|p| is_left(vertices[0], vertices[1], *p) == 0.0) {
+            return Err(PolyError::Degenerate);
+        }
+        validate_simple(&vertices, tolerance)?;
+        Ok(Polygon { vertices })
+    }
+
+    /// Converts the vertices to another scalar type.
+    ///
+    /// Narrowing conversions round each coordinate, so the result is not re-validated.
+    pub fn cast<U: Scalar>(&self) -> Polygon<U> {
+        Polygon { vertices: self.vertices.iter().map(|p| p.cast()).collect() }
+    }
+
+    /// The enclosed area, whichever way the vertices run.
+    pub fn area(&self) -> T {
+        ring_twice_area(&self.vertices).abs() / T::TWO
+    }
+
+    pub fn bounds(&self) -> Aabb<T> {
+        Aabb::from_points(&self.vertices)
+    }
+
+    /// The length of the boundary.
+    pub fn perimeter(&self) -> T {
+        ring_perimeter(&self.vertices)
+    }
+
+    /// Whether `p` is inside the polygon or on its boundary, within `Tolerance::default()`.
+    pub fn is_point_inside(&self, p: Point<T>) -> bool {
+        self.is_point_inside_with_tolerance(p, &Tolerance::default())
+    }
+
+    /// Whether `p` is inside the polygon, on its boundary or within `tolerance` of a vertex.
+    pub fn is_point_inside_with_tolerance(&self, p: Point<T>, tolerance: &Tolerance<T>) -> bool {
+        ring_contains(&self.vertices, p, tolerance)
+    }
+
+    /// Returns every distinct point where `segment` crosses the polygon boundary,
+    /// ordered by distance from `segment.p1`.
+    ///
+    /// Where the segment runs along an edge only the ends of the shared stretch are reported.
+    pub fn intersect_with_segment(&self, segment: &Segment<T>) -> Vec<Point<T>> {
+        self.intersect_with_segment_with_tolerance(segment, &Tolerance::default())
+    }
+
+    /// Like `intersect_with_segment`, comparing points within `tolerance`.
+    pub fn intersect_with_segment_with_tolerance(&self, segment: &Segment<T>, tolerance: &Tolerance<T>) -> Vec<Point<T>> {
+        let n = self.vertices.len();
+        let mut intersections: Vec<Point<T>> = Vec::new();
+        if !bounds_may_touch(&self.bounds(), &segment.bounds(), tolerance) {
+            return intersections;
+        }
+        for i in 0..n {
+            let edge = Segment::new(self.vertices[i], self.vertices[(i + 1) % n]);
+            let found = match edge.intersection_with_tolerance(segment, tolerance) {
+                SegmentIntersection::None => vec![],
+                SegmentIntersection::Point(p) => vec![p],
+                SegmentIntersection::Overlap(shared) => vec![shared.p1, shared.p2],
+            };
+            for p in found {
+                if !intersections.iter().any(|q| tolerance.points_eq(*q, p)) {
+                    intersections.push(p);
+                }
+            }
+        }
+        intersections.sort_by(|a, b| segment.p1.distance(*a).partial_cmp(&segment.p1.distance(*b)).unwrap());
+        intersections
+    }
+}
+
+impl From<Polygon<f32>> for Polygon<f64> {
+    fn from(polygon: Polygon<f32>) -> Polygon<f64> {
+        polygon.cast()
+    }
+}
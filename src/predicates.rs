@@ -0,0 +1,251 @@
+//! Adaptive-precision geometric predicates after Shewchuk, "Adaptive Precision
+//! Floating-Point Arithmetic and Fast Robust Geometric Predicates" (1997).
+//!
+//! Coordinates are widened to `f64`, which is exact for both `f32` and `f64`
+//! inputs, and the determinant is refined with expansion arithmetic only when
+//! the fast floating-point estimate is too close to zero to trust its sign.
+
+use crate::primitives::Point;
+use crate::scalar::Scalar;
+
+/// Which way three points turn.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Orientation {
+    CounterClockwise,
+    Clockwise,
+    Collinear,
+}
+
+const EPSILON: f64 = f64::EPSILON * 0.5;
+const RESULT_ERR_BOUND: f64 = (3.0 + 8.0 * EPSILON) * EPSILON;
+const CCW_ERR_BOUND_A: f64 = (3.0 + 16.0 * EPSILON) * EPSILON;
+const CCW_ERR_BOUND_B: f64 = (2.0 + 12.0 * EPSILON) * EPSILON;
+const CCW_ERR_BOUND_C: f64 = (9.0 + 64.0 * EPSILON) * EPSILON * EPSILON;
+const ICC_ERR_BOUND_A: f64 = (10.0 + 96.0 * EPSILON) * EPSILON;
+
+/// Returns twice the signed area of the triangle `pa`, `pb`, `pc`: positive when
+/// the points turn counter-clockwise, negative when clockwise and zero when
+/// collinear. The magnitude is approximate but the sign is always exact.
+pub fn orient2d<T: Scalar>(pa: Point<T>, pb: Point<T>, pc: Point<T>) -> f64 {
+    let (ax, ay) = (pa.x.to_f64(), pa.y.to_f64());
+    let (bx, by) = (pb.x.to_f64(), pb.y.to_f64());
+    let (cx, cy) = (pc.x.to_f64(), pc.y.to_f64());
+
+    let det_left = (ax - cx) * (by - cy);
+    let det_right = (ay - cy) * (bx - cx);
+    let det = det_left - det_right;
+
+    let det_sum = if det_left > 0.0 {
+        if det_right <= 0.0 {
+            return det;
+        }
+        det_left + det_right
+    } else if det_left < 0.0 {
+        if det_right >= 0.0 {
+            return det;
+        }
+        -det_left - det_right
+    } else {
+        return det;
+    };
+
+    let err_bound = CCW_ERR_BOUND_A * det_sum;
+    if det >= err_bound || -det >= err_bound {
+        return det;
+    }
+    orient2d_adapt([ax, ay], [bx, by], [cx, cy], det_sum)
+}
+
+/// Exact orientation of `pc` relative to the directed line `pa` → `pb`.
+pub fn orientation<T: Scalar>(pa: Point<T>, pb: Point<T>, pc: Point<T>) -> Orientation {
+    let det = orient2d(pa, pb, pc);
+    if det > 0.0 {
+        Orientation::CounterClockwise
+    } else if det < 0.0 {
+        Orientation::Clockwise
+    } else {
+        Orientation::Collinear
+    }
+}
+
+/// Exact sign of the cross product of the vectors `a0` → `a1` and `b0` → `b1`:
+/// positive when turning from the first to the second is counter-clockwise.
+pub(crate) fn cross_sign<T: Scalar>(a0: Point<T>, a1: Point<T>, b0: Point<T>, b1: Point<T>) -> f64 {
+    let ax = exact_diff(a1.x.to_f64(), a0.x.to_f64());
+    let ay = exact_diff(a1.y.to_f64(), a0.y.to_f64());
+    let bx = exact_diff(b1.x.to_f64(), b0.x.to_f64());
+    let by = exact_diff(b1.y.to_f64(), b0.y.to_f64());
+    let left = expansion_product(&ax, &by);
+    let right: Vec<f64> = expansion_product(&ay, &bx).iter().map(|c| -c).collect();
+    most_significant(&expansion_sum(&left, &right))
+}
+
+/// Exact sign of `a * x + b * y - c` for the point `p`.
+pub(crate) fn linear_sign<T: Scalar>(a: T, b: T, c: T, p: Point<T>) -> f64 {
+    let (ax, ax_err) = two_product(a.to_f64(), p.x.to_f64());
+    let (by, by_err) = two_product(b.to_f64(), p.y.to_f64());
+    let sum = expansion_sum(&[ax_err, ax], &[by_err, by]);
+    most_significant(&grow_expansion(&sum, -c.to_f64()))
+}
+
+/// Returns a value that is positive when `pd` lies inside the circle through `pa`, `pb`
+/// and `pc`, negative when outside and zero when on it. The first three points must run
+/// counter-clockwise; the sign flips if they run clockwise. The magnitude is approximate but
+/// the sign is always exact.
+pub fn incircle<T: Scalar>(pa: Point<T>, pb: Point<T>, pc: Point<T>, pd: Point<T>) -> f64 {
+    let (ax, ay) = (pa.x.to_f64(), pa.y.to_f64());
+    let (bx, by) = (pb.x.to_f64(), pb.y.to_f64());
+    let (cx, cy) = (pc.x.to_f64(), pc.y.to_f64());
+    let (dx, dy) = (pd.x.to_f64(), pd.y.to_f64());
+
+    let (adx, ady) = (ax - dx, ay - dy);
+    let (bdx, bdy) = (bx - dx, by - dy);
+    let (cdx, cdy) = (cx - dx, cy - dy);
+
+    let (bdxcdy, cdxbdy) = (bdx * cdy, cdx * bdy);
+    let alift = adx * adx + ady * ady;
+    let (cdxady, adxcdy) = (cdx * ady, adx * cdy);
+    let blift = bdx * bdx + bdy * bdy;
+    let (adxbdy, bdxady) = (adx * bdy, bdx * ady);
+    let clift = cdx * cdx + cdy * cdy;
+
+    let det = alift * (bdxcdy - cdxbdy) + blift * (cdxady - adxcdy) + clift * (adxbdy - bdxady);
+    let permanent = (bdxcdy.abs() + cdxbdy.abs()) * alift + (cdxady.abs() + adxcdy.abs()) * blift + (adxbdy.abs() + bdxady.abs()) * clift;
+    let err_bound = ICC_ERR_BOUND_A * permanent;
+    if det > err_bound || -det > err_bound {
+        return det;
+    }
+    incircle_exact([ax, ay], [bx, by], [cx, cy], [dx, dy])
+}
+
+/// The incircle determinant evaluated exactly, for when the estimate is too close to zero.
+/// Only its sign is meaningful.
+fn incircle_exact(pa: [f64; 2], pb: [f64; 2], pc: [f64; 2], pd: [f64; 2]) -> f64 {
+    let relative = |p: [f64; 2]| (exact_diff(p[0], pd[0]), exact_diff(p[1], pd[1]));
+    let ((adx, ady), (bdx, bdy), (cdx, cdy)) = (relative(pa), relative(pb), relative(pc));
+    let lift = |x: &[f64], y: &[f64]| expansion_sum(&expansion_product(x, x), &expansion_product(y, y));
+    let cross = |x1: &[f64], y1: &[f64], x2: &[f64], y2: &[f64]| {
+        let right: Vec<f64> = expansion_product(y1, x2).iter().map(|c| -c).collect();
+        expansion_sum(&expansion_product(x1, y2), &right)
+    };
+    let a = expansion_product(&lift(&adx, &ady), &cross(&bdx, &bdy, &cdx, &cdy));
+    let b = expansion_product(&lift(&bdx, &bdy), &cross(&cdx, &cdy, &adx, &ady));
+    let c = expansion_product(&lift(&cdx, &cdy), &cross(&adx, &ady, &bdx, &bdy));
+    most_significant(&expansion_sum(&expansion_sum(&a, &b), &c))
+}
+
+fn orient2d_adapt(pa: [f64; 2], pb: [f64; 2], pc: [f64; 2], det_sum: f64) -> f64 {
+    let acx = pa[0] - pc[0];
+    let bcx = pb[0] - pc[0];
+    let acy = pa[1] - pc[1];
+    let bcy = pb[1] - pc[1];
+
+    let b = two_two_diff(two_product(acx, bcy), two_product(acy, bcx));
+    let mut det = estimate(&b);
+    let err_bound = CCW_ERR_BOUND_B * det_sum;
+    if det >= err_bound || -det >= err_bound {
+        return det;
+    }
+
+    let acx_tail = two_diff_tail(pa[0], pc[0], acx);
+    let bcx_tail = two_diff_tail(pb[0], pc[0], bcx);
+    let acy_tail = two_diff_tail(pa[1], pc[1], acy);
+    let bcy_tail = two_diff_tail(pb[1], pc[1], bcy);
+    if acx_tail == 0.0 && acy_tail == 0.0 && bcx_tail == 0.0 && bcy_tail == 0.0 {
+        return det;
+    }
+
+    let err_bound = CCW_ERR_BOUND_C * det_sum + RESULT_ERR_BOUND * det.abs();
+    det += (acx * bcy_tail + bcy * acx_tail) - (acy * bcx_tail + bcx * acy_tail);
+    if det >= err_bound || -det >= err_bound {
+        return det;
+    }
+
+    let u = two_two_diff(two_product(acx_tail, bcy), two_product(acy_tail, bcx));
+    let c1 = expansion_sum(&b, &u);
+    let u = two_two_diff(two_product(acx, bcy_tail), two_product(acy, bcx_tail));
+    let c2 = expansion_sum(&c1, &u);
+    let u = two_two_diff(two_product(acx_tail, bcy_tail), two_product(acy_tail, bcx_tail));
+    let d = expansion_sum(&c2, &u);
+
+    most_significant(&d)
+}
+
+/// `a + b` as a rounded sum and its exact error.
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    let b_virtual = x - a;
+    let a_virtual = x - b_virtual;
+    let b_round = b - b_virtual;
+    let a_round = a - a_virtual;
+    (x, a_round + b_round)
+}
+
+fn two_diff_tail(a: f64, b: f64, x: f64) -> f64 {
+    let b_virtual = a - x;
+    let a_virtual = x + b_virtual;
+    let b_round = b_virtual - b;
+    let a_round = a - a_virtual;
+    a_round + b_round
+}
+
+/// `a * b` as a rounded product and its exact error, using a fused multiply-add.
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let x = a * b;
+    (x, a.mul_add(b, -x))
+}
+
+/// `a - b` as a two-component expansion.
+fn exact_diff(a: f64, b: f64) -> [f64; 2] {
+    let x = a - b;
+    [two_diff_tail(a, b, x), x]
+}
+
+/// Multiplies two expansions exactly.
+fn expansion_product(e: &[f64], f: &[f64]) -> Vec<f64> {
+    let mut h = Vec::new();
+    for &x in e {
+        for &y in f {
+            let (product, err) = two_product(x, y);
+            h = expansion_sum(&h, &[err, product]);
+        }
+    }
+    h
+}
+
+/// `(a.0 + a.1) - (b.0 + b.1)` as a four-component expansion.
+fn two_two_diff(a: (f64, f64), b: (f64, f64)) -> Vec<f64> {
+    expansion_sum(&[a.1, a.0], &[-b.1, -b.0])
+}
+
+/// Adds `b` to the nonoverlapping expansion `e` exactly, dropping zero components so that
+/// products of expansions stay short.
+fn grow_expansion(e: &[f64], b: f64) -> Vec<f64> {
+    let mut h = Vec::with_capacity(e.len() + 1);
+    let mut q = b;
+    for &component in e {
+        let (sum, err) = two_sum(q, component);
+        if err != 0.0 {
+            h.push(err);
+        }
+        q = sum;
+    }
+    if q != 0.0 {
+        h.push(q);
+    }
+    h
+}
+
+/// Adds two nonoverlapping expansions exactly.
+fn expansion_sum(e: &[f64], f: &[f64]) -> Vec<f64> {
+    f.iter().fold(e.to_vec(), |h, &component| grow_expansion(&h, component))
+}
+
+fn estimate(e: &[f64]) -> f64 {
+    e.iter().sum()
+}
+
+/// The largest nonzero component, which carries the sign of the expansion.
+fn most_significant(e: &[f64]) -> f64 {
+    e.iter().rev().copied().find(|&c| c != 0.0).unwrap_or(0.0)
+}
@@ -0,0 +1,367 @@
+use crate::predicates::{linear_sign, orient2d, Orientation};
+use crate::scalar::Scalar;
+use crate::tolerance::Tolerance;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Line<T> {
+    pub a: T,
+    pub b: T,
+    pub c: T,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Segment<T> {
+    pub p1: Point<T>,
+    pub p2: Point<T>,
+}
+
+/// Where two segments meet.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SegmentIntersection<T> {
+    None,
+    /// The segments cross or touch at a single point.
+    Point(Point<T>),
+    /// The segments are collinear and share this stretch, which runs the same way as the first segment.
+    Overlap(Segment<T>),
+}
+
+impl<T: Scalar> SegmentIntersection<T> {
+    pub fn is_none(&self) -> bool {
+        matches!(self, SegmentIntersection::None)
+    }
+}
+
+/// Positive when `p2` is left of the directed line `p0` → `p1`, negative when it is
+/// right and zero when the three points are collinear. The sign is exact.
+pub(crate) fn is_left<T: Scalar>(p0: Point<T>, p1: Point<T>, p2: Point<T>) -> f64 {
+    orient2d(p0, p1, p2)
+}
+
+impl<T: Scalar> Point<T> {
+    pub fn new(x: T, y: T) -> Point<T> {
+        Point { x, y }
+    }
+
+    /// Converts the coordinates to another scalar type.
+    pub fn cast<U: Scalar>(self) -> Point<U> {
+        Point::new(self.x.cast(), self.y.cast())
+    }
+
+    pub fn distance(self, other: Point<T>) -> T {
+        let (dx, dy) = (other.x - self.x, other.y - self.y);
+        (dx * dx + dy * dy).sqrt()
+    }
+}
+
+impl From<Point<f32>> for Point<f64> {
+    fn from(p: Point<f32>) -> Point<f64> {
+        p.cast()
+    }
+}
+
+impl<T: Scalar> Line<T> {
+    pub fn new(a: T, b: T, c: T) -> Line<T> {
+        Line { a, b, c }
+    }
+    pub fn from_points(p1: Point<T>, p2: Point<T>) -> Line<T> {
+        let a = p2.y - p1.y;
+        let b = p1.x - p2.x;
+        let c = a * p1.x + b * p1.y;
+        Line::new(a, b, c)
+    }
+
+    /// Converts the coefficients to another scalar type.
+    pub fn cast<U: Scalar>(self) -> Line<U> {
+        Line::new(self.a.cast(), self.b.cast(), self.c.cast())
+    }
+
+    /// The direction `(-b, a)`, which for `from_points(p1, p2)` points from `p1` to `p2`.
+    pub fn direction(&self) -> Point<T> {
+        Point::new(-self.b, self.a)
+    }
+
+    /// The same line scaled so that `a² + b² = 1`, which makes `a * x + b * y - c` the signed
+    /// distance from the line. Coefficients with `a` and `b` both zero come back unchanged.
+    pub fn normalize(&self) -> Line<T> {
+        let length = (self.a * self.a + self.b * self.b).sqrt();
+        if length == T::ZERO {
+            return *self;
+        }
+        Line::new(self.a / length, self.b / length, self.c / length)
+    }
+
+    /// The distance from the line to `p`, positive on the side the normal `(a, b)` points to,
+    /// which for `from_points(p1, p2)` is the right of `p1` → `p2`.
+    pub fn signed_distance_to_point(&self, p: Point<T>) -> T {
+        let line = self.normalize();
+        line.a * p.x + line.b * p.y - line.c
+    }
+
+    pub fn distance_to_point(&self, p: Point<T>) -> T {
+        self.signed_distance_to_point(p).abs()
+    }
+
+    /// The point on the line nearest to `p`, its orthogonal projection.
+    pub fn closest_point(&self, p: Point<T>) -> Point<T> {
+        let line = self.normalize();
+        let distance = line.a * p.x + line.b * p.y - line.c;
+        Point::new(p.x - distance * line.a, p.y - distance * line.b)
+    }
+
+    /// Which way `p` lies from the line, looking along its `direction`: `CounterClockwise`
+    /// for the left, `Clockwise` for the right and `Collinear` for on it.
+    ///
+    /// The sign of `a * x + b * y - c` is evaluated exactly, but `from_points` rounds the
+    /// coefficients it computes, so for points very close to a line through two points
+    /// `orientation` of the three points is the one to trust.
+    pub fn side(&self, p: Point<T>) -> Orientation {
+        let value = linear_sign(self.a, self.b, self.c, p);
+        if value < 0.0 {
+            Orientation::CounterClockwise
+        } else if value > 0.0 {
+            Orientation::Clockwise
+        } else {
+            Orientation::Collinear
+        }
+    }
+
+    /// The point where the lines cross, or `None` if they are parallel.
+    pub fn intersection(&self, other: &Line<T>) -> Option<Point<T>> {
+        intersect_lines(self, other)
+    }
+
+    /// The line through `p` parallel to this one, with the same direction.
+    pub fn parallel_through(&self, p: Point<T>) -> Line<T> {
+        Line::new(self.a, self.b, self.a * p.x + self.b * p.y)
+    }
+
+    /// The line through `p` perpendicular to this one, with its direction turned a quarter
+    /// turn counter-clockwise from this one's.
+    pub fn perpendicular_through(&self, p: Point<T>) -> Line<T> {
+        let (a, b) = (-self.b, self.a);
+        Line::new(a, b, a * p.x + b * p.y)
+    }
+
+    /// The angle between the lines in radians, from 0 for parallel lines to π/2 for
+    /// perpendicular ones.
+    pub fn angle_between(&self, other: &Line<T>) -> T {
+        let cross = self.a * other.b - self.b * other.a;
+        let dot = self.a * other.a + self.b * other.b;
+        cross.abs().atan2(dot.abs())
+    }
+}
+
+impl From<Line<f32>> for Line<f64> {
+    fn from(l: Line<f32>) -> Line<f64> {
+        l.cast()
+    }
+}
+
+impl<T: Scalar> Segment<T> {
+    pub fn new(p1: Point<T>, p2: Point<T>) -> Segment<T> {
+        Segment { p1, p2 }
+    }
+
+    /// Converts the end points to another scalar type.
+    pub fn cast<U: Scalar>(self) -> Segment<U> {
+        Segment::new(self.p1.cast(), self.p2.cast())
+    }
+
+    pub fn length(&self) -> T {
+        self.p1.distance(self.p2)
+    }
+
+    /// The point on the segment nearest to `p`.
+    pub fn closest_point(&self, p: Point<T>) -> Point<T> {
+        let (dx, dy) = (self.p2.x - self.p1.x, self.p2.y - self.p1.y);
+        let length_squared = dx * dx + dy * dy;
+        if length_squared == T::ZERO {
+            return self.p1;
+        }
+        let t = ((p.x - self.p1.x) * dx + (p.y - self.p1.y) * dy) / length_squared;
+        let t = t.max(T::ZERO).min(T::ONE);
+        Point::new(self.p1.x + t * dx, self.p1.y + t * dy)
+    }
+
+    pub fn distance_to_point(&self, p: Point<T>) -> T {
+        self.closest_point(p).distance(p)
+    }
+
+    /// Where this segment meets `other`, including the stretch they share if they are
+    /// collinear.
+    ///
+    /// Uses `Tolerance::default()`; see `intersection_with_tolerance`.
+    pub fn intersection(&self, other: &Segment<T>) -> SegmentIntersection<T> {
+        self.intersection_with_tolerance(other, &Tolerance::default())
+    }
+
+    /// Where this segment meets `other`, snapping end points within `tolerance` of each other.
+    ///
+    /// Whether the segments are collinear, touch or cross is decided with exact orientation
+    /// tests, so the answer is consistent with `check_polygon_is_convex` and
+    /// `ConvexPolygon::is_point_inside`. The tolerance only merges nearby points: an end point
+    /// within `tolerance` of an end of the other segment is returned as the intersection, and
+    /// the ends of a shared stretch that close together give a single point. Only the location
+    /// of a proper crossing is subject to rounding, and it is kept within both segments.
+    /// A segment of zero length meets nothing.
+    pub fn intersection_with_tolerance(&self, other: &Segment<T>, tolerance: &Tolerance<T>) -> SegmentIntersection<T> {
+        let (seg_one, seg_two) = (self, other);
+        if seg_one.p1 == seg_one.p2 || seg_two.p1 == seg_two.p2 {
+            // Zero length
+            return SegmentIntersection::None;
+        }
+        if is_left(seg_one.p1, seg_one.p2, seg_two.p1) == 0.0 && is_left(seg_one.p1, seg_one.p2, seg_two.p2) == 0.0 {
+            return collinear_overlap(seg_one, seg_two, tolerance);
+        }
+
+        // An end point lying on the other segment, or snapping to one of its ends, is the intersection.
+        for (p, s) in [(seg_two.p1, seg_one), (seg_two.p2, seg_one), (seg_one.p1, seg_two), (seg_one.p2, seg_two)] {
+            if near_segment(p, s, tolerance) {
+                return SegmentIntersection::Point(p);
+            }
+        }
+
+        let o1 = is_left(seg_one.p1, seg_one.p2, seg_two.p1);
+        let o2 = is_left(seg_one.p1, seg_one.p2, seg_two.p2);
+        let o3 = is_left(seg_two.p1, seg_two.p2, seg_one.p1);
+        let o4 = is_left(seg_two.p1, seg_two.p2, seg_one.p2);
+        if (o1 > 0.0) == (o2 > 0.0) || (o3 > 0.0) == (o4 > 0.0) {
+            // Both ends of one segment are on the same side of the other
+            return SegmentIntersection::None;
+        }
+
+        let line_one = Line::from_points(seg_one.p1, seg_one.p2);
+        let line_two = Line::from_points(seg_two.p1, seg_two.p2);
+        let p = intersect_lines(&line_one, &line_two).unwrap_or_else(|| {
+            // Too close to parallel for the determinant, so interpolate along seg_one instead
+            let t = T::from_f64(o3 / (o3 - o4));
+            Point::new(seg_one.p1.x + t * (seg_one.p2.x - seg_one.p1.x), seg_one.p1.y + t * (seg_one.p2.y - seg_one.p1.y))
+        });
+        if on_segment(p, seg_one) && on_segment(p, seg_two) {
+            return SegmentIntersection::Point(p);
+        }
+
+        SegmentIntersection::Point(clamp_to_segment(clamp_to_segment(p, seg_one), seg_two))
+    }
+}
+
+impl From<Segment<f32>> for Segment<f64> {
+    fn from(s: Segment<f32>) -> Segment<f64> {
+        s.cast()
+    }
+}
+
+fn value_between<T: Scalar>(x: T, min: T, max: T) -> bool {
+    x >= min && x <= max
+}
+
+fn value_between_bidirectional<T: Scalar>(x: T, a: T, b: T) -> bool {
+    value_between(x, a, b) || value_between(x, b, a)
+}
+
+fn clamp_between_bidirectional<T: Scalar>(x: T, a: T, b: T) -> T {
+    x.max(a.min(b)).min(a.max(b))
+}
+
+fn on_segment<T: Scalar>(p: Point<T>, s: &Segment<T>) -> bool {
+    let (q , r) = (s.p1, s.p2);
+    value_between_bidirectional(p.x, q.x, r.x) && value_between_bidirectional(p.y, q.y, r.y)
+}
+
+/// Pulls a computed crossing point back inside the bounding box of `s`, undoing rounding error.
+fn clamp_to_segment<T: Scalar>(p: Point<T>, s: &Segment<T>) -> Point<T> {
+    let (q , r) = (s.p1, s.p2);
+    Point::new(clamp_between_bidirectional(p.x, q.x, r.x), clamp_between_bidirectional(p.y, q.y, r.y))
+}
+
+/// Whether `p` lies on the segment `s`, which is decided exactly, or is within `tolerance`
+/// of one of its ends and so snaps to it.
+pub(crate) fn near_segment<T: Scalar>(p: Point<T>, s: &Segment<T>, tolerance: &Tolerance<T>) -> bool {
+    (is_left(s.p1, s.p2, p) == 0.0 && on_segment(p, s)) || tolerance.points_eq(p, s.p1) || tolerance.points_eq(p, s.p2)
+}
+
+/// The unit normal of the edge `a` → `b` pointing to its right, which is out of a
+/// counter-clockwise ring.
+pub(crate) fn outward_normal<T: Scalar>(a: Point<T>, b: Point<T>) -> Point<T> {
+    let length = a.distance(b);
+    Point::new((b.y - a.y) / length, (a.x - b.x) / length)
+}
+
+/// The point where two lines meet, or `None` if the determinant is zero because they are
+/// parallel or too nearly so.
+pub(crate) fn intersect_lines<T: Scalar>(line_one: &Line<T>, line_two: &Line<T>) -> Option<Point<T>> {
+    let a1 = line_one.a;
+    let b1 = line_one.b;
+    let c1 = line_one.c;
+
+    let a2 = line_two.a;
+    let b2 = line_two.b;
+    let c2 = line_two.c;
+
+    let det = a1 * b2 - a2 * b1;
+    if det == T::ZERO {
+        return None;
+    }
+    let x = (b2 * c1 - b1 * c2) / det;
+    let y = (a1 * c2 - a2 * c1) / det;
+    Some(Point { x, y })
+}
+
+/// The stretch shared by two segments already known to be collinear. Its ends are end
+/// points of the inputs rather than computed points, so they carry no rounding error.
+fn collinear_overlap<T: Scalar>(seg_one: &Segment<T>, seg_two: &Segment<T>, tolerance: &Tolerance<T>) -> SegmentIntersection<T> {
+    let (dx, dy) = (seg_one.p2.x - seg_one.p1.x, seg_one.p2.y - seg_one.p1.y);
+    // Position along seg_one, with its ends at 0 and 1
+    let along = |p: Point<T>| ((p.x - seg_one.p1.x) * dx + (p.y - seg_one.p1.y) * dy) / (dx * dx + dy * dy);
+    let (mut t_min, mut t_max) = (along(seg_two.p1), along(seg_two.p2));
+    let (mut q_min, mut q_max) = (seg_two.p1, seg_two.p2);
+    if t_min > t_max {
+        (t_min, t_max) = (t_max, t_min);
+        (q_min, q_max) = (q_max, q_min);
+    }
+    let (start, t_start) = if t_min > T::ZERO { (q_min, t_min) } else { (seg_one.p1, T::ZERO) };
+    let (end, t_end) = if t_max < T::ONE { (q_max, t_max) } else { (seg_one.p2, T::ONE) };
+    if tolerance.points_eq(start, end) {
+        SegmentIntersection::Point(start)
+    } else if t_start > t_end {
+        SegmentIntersection::None
+    } else {
+        SegmentIntersection::Overlap(Segment::new(start, end))
+    }
+}
+
+/// Returns the point where the segments cross, or `None` if they don't or if they share
+/// a stretch of more than one point.
+///
+/// Uses `Tolerance::default()`; see `intersect_line_segments_with_tolerance`.
+pub fn intersect_line_segments<T: Scalar>(seg_one: &Segment<T>, seg_two: &Segment<T>) -> Option<Point<T>> {
+    intersect_line_segments_with_tolerance(seg_one, seg_two, &Tolerance::default())
+}
+
+/// Returns the point where the segments cross, or `None` if they don't or if they share
+/// a stretch of more than one point.
+///
+/// This is `Segment::intersection_with_tolerance` without the `Overlap` case; use that
+/// when collinear segments matter.
+pub fn intersect_line_segments_with_tolerance<T: Scalar>(seg_one: &Segment<T>, seg_two: &Segment<T>, tolerance: &Tolerance<T>) -> Option<Point<T>> {
+    match seg_one.intersection_with_tolerance(seg_two, tolerance) {
+        SegmentIntersection::Point(p) => Some(p),
+        SegmentIntersection::None | SegmentIntersection::Overlap(_) => None,
+    }
+}
+
+pub(crate) fn dedup_vertices<T: Scalar>(vertices: &[Point<T>], tolerance: &Tolerance<T>) -> Vec<Point<T>> {
+    let mut vertices_copy: Vec<Point<T>> = vec![];
+    for p in vertices {
+        if !vertices_copy.iter().any(|q| tolerance.points_eq(*p, *q)) {
+            vertices_copy.push(*p);
+        }
+    }
+    vertices_copy
+}
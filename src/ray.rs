@@ -0,0 +1,93 @@
+//! Casting rays against convex polygons.
+
+use crate::boolean::orientation_sign;
+use crate::convex::ConvexPolygon;
+use crate::primitives::{outward_normal, Point};
+use crate::scalar::Scalar;
+
+/// A half-line from `origin` along `direction`, which need not have unit length.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Ray<T> {
+    pub origin: Point<T>,
+    pub direction: Point<T>,
+}
+
+/// Where a ray first meets a polygon's boundary.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Hit<T> {
+    /// How far along the ray, in multiples of its direction.
+    pub t: T,
+    pub point: Point<T>,
+    /// The unit normal of the edge hit, pointing out of the polygon.
+    pub normal: Point<T>,
+    /// The edge hit, which runs from vertex `edge` to vertex `edge + 1`.
+    pub edge: usize,
+}
+
+impl<T: Scalar> Ray<T> {
+    pub fn new(origin: Point<T>, direction: Point<T>) -> Ray<T> {
+        Ray { origin, direction }
+    }
+
+    /// Converts the origin and direction to another scalar type. Every other type calls
+    /// this `cast`, but here that name is taken by casting the ray against a polygon.
+    pub fn cast_scalar<U: Scalar>(self) -> Ray<U> {
+        Ray::new(self.origin.cast(), self.direction.cast())
+    }
+
+    /// The point `t` multiples of the direction along the ray.
+    pub fn at(&self, t: T) -> Point<T> {
+        Point::new(self.origin.x + t * self.direction.x, self.origin.y + t * self.direction.y)
+    }
+
+    /// Where the ray first meets the boundary of `polygon`, or `None` if it misses.
+    ///
+    /// A ray starting inside the polygon hits the edge it leaves through. A ray that only
+    /// grazes a vertex or runs along an edge hits it where it first touches.
+    pub fn cast(&self, polygon: &ConvexPolygon<T>) -> Option<Hit<T>> {
+        let clip = polygon.clip_ray(self.origin, self.direction)?;
+        let (t, edge) = match (clip.entry_edge, clip.exit_edge) {
+            (Some(edge), _) => (clip.t0, edge),
+            (None, Some(edge)) => (clip.t1, edge),
+            // A zero direction meets no edge
+            (None, None) => return None,
+        };
+        let vertices = &polygon.vertices;
+        let (a, b) = (vertices[edge], vertices[(edge + 1) % vertices.len()]);
+        let (right, sign) = (outward_normal(a, b), T::from_f64(orientation_sign(vertices)));
+        let normal = Point::new(sign * right.x, sign * right.y);
+        Some(Hit { t, point: self.at(t), normal, edge })
+    }
+
+    /// The nearest hit among `polygons`, with the index of the polygon hit. Ties go to the
+    /// earlier polygon.
+    pub fn cast_nearest(&self, polygons: &[ConvexPolygon<T>]) -> Option<(usize, Hit<T>)> {
+        polygons
+            .iter()
+            .enumerate()
+            .filter_map(|(i, polygon)| self.cast(polygon).map(|hit| (i, hit)))
+            .fold(None, |nearest: Option<(usize, Hit<T>)>, (i, hit)| match nearest {
+                Some((_, best)) if best.t <= hit.t => nearest,
+                _ => Some((i, hit)),
+            })
+    }
+}
+
+impl<T: Scalar> Hit<T> {
+    /// Converts the distance, point and normal to another scalar type.
+    pub fn cast<U: Scalar>(self) -> Hit<U> {
+        Hit { t: self.t.cast(), point: self.point.cast(), normal: self.normal.cast(), edge: self.edge }
+    }
+}
+
+impl From<Ray<f32>> for Ray<f64> {
+    fn from(ray: Ray<f32>) -> Ray<f64> {
+        ray.cast_scalar()
+    }
+}
+
+impl From<Hit<f32>> for Hit<f64> {
+    fn from(hit: Hit<f32>) -> Hit<f64> {
+        hit.cast()
+    }
+}
@@ -0,0 +1,74 @@
+//! Separating Axis Theorem tests between convex polygons.
+//!
+//! Two convex polygons are disjoint exactly when the line through one of their
+//! edges has the whole of the other polygon strictly on its outer side, so only
+//! the edge normals need to be tried as axes.
+
+use crate::boolean::orientation_sign;
+use crate::convex::ConvexPolygon;
+use crate::primitives::{is_left, outward_normal, Point};
+use crate::scalar::Scalar;
+
+/// Whether some edge of `a` has every vertex of `b` strictly outside it. The sign test
+/// is exact, so polygons that only touch are never separated.
+fn has_separating_edge<T: Scalar>(a: &[Point<T>], b: &[Point<T>]) -> bool {
+    let n = a.len();
+    let outside = orientation_sign(a);
+    (0..n).any(|i| b.iter().all(|v| outside * is_left(a[i], a[(i + 1) % n], *v) < 0.0))
+}
+
+/// The smallest and largest values of `p · axis` over the vertices.
+fn project<T: Scalar>(vertices: &[Point<T>], axis: Point<T>) -> (T, T) {
+    vertices.iter().fold((T::MAX, T::MIN), |(lo, hi), v| {
+        let d = v.x * axis.x + v.y * axis.y;
+        (lo.min(d), hi.max(d))
+    })
+}
+
+impl<T: Scalar> ConvexPolygon<T> {
+    /// Whether the polygons share any point, including polygons that only touch.
+    ///
+    /// Much cheaper than `intersection` when the shape of the overlap is not needed.
+    pub fn overlaps(&self, other: &ConvexPolygon<T>) -> bool {
+        if self.vertices.is_empty() || other.vertices.is_empty() {
+            return false;
+        }
+        !has_separating_edge(&self.vertices, &other.vertices) && !has_separating_edge(&other.vertices, &self.vertices)
+    }
+
+    /// The minimum translation that separates the polygons, as a unit `normal` and a `depth`,
+    /// or `None` if they do not overlap. Moving `other` by `normal * depth`, or `self` by
+    /// `-normal * depth`, leaves them touching. Polygons that already only touch have depth zero.
+    ///
+    /// The normal is returned as a `Point` holding the direction's components.
+    pub fn penetration(&self, other: &ConvexPolygon<T>) -> Option<(Point<T>, T)> {
+        if !self.overlaps(other) {
+            return None;
+        }
+        let mut best: Option<(Point<T>, T)> = None;
+        for vertices in [&self.vertices, &other.vertices] {
+            let n = vertices.len();
+            for i in 0..n {
+                let (a, b) = (vertices[i], vertices[(i + 1) % n]);
+                if a == b {
+                    continue;
+                }
+                let axis = outward_normal(a, b);
+                let (min_self, max_self) = project(&self.vertices, axis);
+                let (min_other, max_other) = project(&other.vertices, axis);
+                // Push `other` forwards along the axis, or backwards, whichever is shorter
+                let forwards = max_self - min_other;
+                let backwards = max_other - min_self;
+                let (normal, depth) = if forwards <= backwards {
+                    (axis, forwards)
+                } else {
+                    (Point::new(-axis.x, -axis.y), backwards)
+                };
+                if best.is_none_or(|(_, best_depth)| depth < best_depth) {
+                    best = Some((normal, depth.max(T::ZERO)));
+                }
+            }
+        }
+        best
+    }
+}
@@ -0,0 +1,53 @@
+use std::iter::zip;
+
+use crate::aabb::Aabb;
+use crate::convex::ConvexPolygon;
+use crate::multipolygon::MultiPolygon;
+use crate::primitives::Point;
+use crate::scalar::Scalar;
+
+/// The opening `<svg>` tag, with the view box fitted to `points` unless one is given.
+fn svg_header<'a, T: Scalar>(points: impl IntoIterator<Item = &'a Point<T>>, width: u32, height: u32, view_box: Option<(Point<T>, Point<T>)>) -> String {
+    let (min, max) = view_box.unwrap_or_else(|| {
+        let bounds = Aabb::from_points(points);
+        (bounds.min, bounds.max)
+    });
+    let (min_x, min_y) = (min.x, min.y);
+    let view_width = max.x - min.x;
+    let view_height = max.y - min.y;
+    format!("<svg width=\"{width}\" height=\"{height}\" xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{min_x} {min_y} {view_width} {view_height}\">")
+}
+
+pub fn generate_svg_from_polygons<T: Scalar>(polygons: &Vec<&ConvexPolygon<T>>, colors: &Vec<&str>, width: u32, height: u32, view_box: Option<(Point<T>,Point<T>)>) -> String {
+    let mut svg = svg_header(polygons.iter().flat_map(|polygon| &polygon.vertices), width, height, view_box);
+    for (polygon, color) in zip(polygons, colors) {
+        let mut points = String::new();
+        for vertex in &polygon.vertices {
+            points.push_str(&format!("{},{} ", vertex.x, vertex.y));
+        }
+        svg.push_str(&format!("<polygon points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"0.1\" />", points, color));
+    }
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Like `generate_svg_from_polygons`, but draws each multipolygon as a single `<path>`
+/// with one closed subpath per ring. The `evenodd` fill rule leaves the holes unfilled.
+pub fn generate_svg_from_multipolygons<T: Scalar>(multipolygons: &[&MultiPolygon<T>], colors: &[&str], width: u32, height: u32, view_box: Option<(Point<T>, Point<T>)>) -> String {
+    let points = multipolygons.iter().flat_map(|multi| &multi.polygons).flat_map(|polygon| &polygon.exterior.vertices);
+    let mut svg = svg_header(points, width, height, view_box);
+    for (multi, color) in zip(multipolygons, colors) {
+        let mut path = String::new();
+        for polygon in &multi.polygons {
+            for ring in std::iter::once(&polygon.exterior).chain(&polygon.interiors) {
+                for (i, vertex) in ring.vertices.iter().enumerate() {
+                    path.push_str(&format!("{}{},{} ", if i == 0 { "M" } else { "L" }, vertex.x, vertex.y));
+                }
+                path.push_str("Z ");
+            }
+        }
+        svg.push_str(&format!("<path d=\"{}\" fill=\"{}\" fill-opacity=\"0.3\" fill-rule=\"evenodd\" stroke=\"{}\" stroke-width=\"0.1\" />", path.trim_end(), color, color));
+    }
+    svg.push_str("</svg>");
+    svg
+}
@@ -0,0 +1,54 @@
+use crate::primitives::Point;
+use crate::scalar::Scalar;
+
+/// How far apart two coordinates may be and still compare equal.
+///
+/// The allowed difference is `absolute + relative * scale`, where `scale` is the
+/// largest coordinate magnitude taking part in the comparison, so the same
+/// tolerance works for geometry near the origin and at survey-scale offsets.
+///
+/// A tolerance only snaps and merges nearby points, such as an end point landing next
+/// to a vertex. Which side of a line a point is on, and whether two segments cross,
+/// is always decided exactly.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Tolerance<T> {
+    pub absolute: T,
+    pub relative: T,
+}
+
+impl<T: Scalar> Tolerance<T> {
+    pub fn new(absolute: T, relative: T) -> Tolerance<T> {
+        Tolerance { absolute, relative }
+    }
+
+    /// No tolerance at all: only exactly equal values compare equal.
+    pub fn exact() -> Tolerance<T> {
+        Tolerance::new(T::ZERO, T::ZERO)
+    }
+
+    /// The allowed difference between values whose magnitude is at most `scale`.
+    pub fn epsilon_at(&self, scale: T) -> T {
+        self.absolute + self.relative * scale.abs()
+    }
+
+    pub fn eq(&self, a: T, b: T) -> bool {
+        (a - b).abs() <= self.epsilon_at(a.abs().max(b.abs()))
+    }
+
+    pub fn points_eq(&self, p: Point<T>, q: Point<T>) -> bool {
+        let eps = self.epsilon_at(max_abs_coordinate(&[p, q]));
+        (p.x - q.x).abs() <= eps && (p.y - q.y).abs() <= eps
+    }
+}
+
+impl<T: Scalar> Default for Tolerance<T> {
+    /// A few ulps relative to the coordinates involved, plus a tiny absolute floor so
+    /// that values which should be exactly zero still match.
+    fn default() -> Tolerance<T> {
+        Tolerance::new(T::EPSILON * T::EPSILON, T::EPSILON * T::from_f64(4.0))
+    }
+}
+
+pub(crate) fn max_abs_coordinate<T: Scalar>(points: &[Point<T>]) -> T {
+    points.iter().fold(T::ZERO, |m, p| m.max(p.x.abs()).max(p.y.abs()))
+}
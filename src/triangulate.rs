@@ -0,0 +1,87 @@
+//! Splitting polygons into triangles.
+//!
+//! Convex polygons are split into a fan from their first vertex. Simple polygons
+//! are split by ear clipping: a vertex whose triangle with its two neighbours
+//! lies inside the polygon is an ear, and cutting it off leaves a smaller simple
+//! polygon. Every simple polygon with more than three vertices has at least two.
+
+use crate::convex::ConvexPolygon;
+use crate::polygon::{ring_twice_area, Polygon};
+use crate::primitives::{is_left, Point};
+use crate::scalar::Scalar;
+
+impl<T: Scalar> ConvexPolygon<T> {
+    /// The triangles `[0, i, i + 1]` fanning out from the first vertex, as indices into
+    /// `vertices`. They run the same way as the polygon, counter-clockwise as `new` leaves it.
+    pub fn triangulate(&self) -> Vec<[usize; 3]> {
+        (1..self.vertices.len().saturating_sub(1)).map(|i| [0, i, i + 1]).collect()
+    }
+}
+
+impl<T: Scalar> Polygon<T> {
+    /// Triangles covering the polygon without overlapping, as indices into `vertices`.
+    ///
+    /// Every triangle runs counter-clockwise, whichever way the polygon does. There are at
+    /// most `n - 2` of them: when no ear is left, a vertex lying on the straight line between
+    /// its neighbours is cut off without a triangle. Takes O(n²) time.
+    pub fn triangulate(&self) -> Vec<[usize; 3]> {
+        let vertices = &self.vertices;
+        let n = vertices.len();
+        if n < 3 {
+            return Vec::new();
+        }
+        // A doubly linked ring of the vertices left, walked counter-clockwise
+        let clockwise = ring_twice_area(vertices) < T::ZERO;
+        let mut next: Vec<usize> = (0..n).map(|i| if clockwise { (i + n - 1) % n } else { (i + 1) % n }).collect();
+        let mut previous = vec![0; n];
+        for i in 0..n {
+            previous[next[i]] = i;
+        }
+        let mut removed = vec![false; n];
+        let mut ear: Vec<bool> = (0..n).map(|i| is_ear(vertices, &removed, previous[i], i, next[i])).collect();
+
+        let mut triangles = Vec::with_capacity(n - 2);
+        let (mut remaining, mut current, mut skipped) = (n, 0, 0);
+        while remaining > 3 {
+            let (a, c) = (previous[current], next[current]);
+            let flat = is_left(vertices[a], vertices[current], vertices[c]) == 0.0;
+            // Vertices between collinear neighbours are never ears, so cut them once a whole
+            // lap finds none. A ring that is not simple may have no ears at all; then cut
+            // whatever comes next rather than loop forever.
+            if ear[current] || (skipped > remaining && (flat || skipped > 2 * remaining)) {
+                if !flat {
+                    triangles.push([a, current, c]);
+                }
+                next[a] = c;
+                previous[c] = a;
+                removed[current] = true;
+                remaining -= 1;
+                ear[a] = is_ear(vertices, &removed, previous[a], a, c);
+                ear[c] = is_ear(vertices, &removed, a, c, next[c]);
+                current = c;
+                skipped = 0;
+            } else {
+                current = next[current];
+                skipped += 1;
+            }
+        }
+        let (a, c) = (previous[current], next[current]);
+        if is_left(vertices[a], vertices[current], vertices[c]) != 0.0 {
+            triangles.push([a, current, c]);
+        }
+        triangles
+    }
+}
+
+/// Whether the triangle `a`, `b`, `c` turns left at `b` and has no other vertex left in
+/// the ring inside it or on its edges, so that cutting it off leaves a simple polygon.
+fn is_ear<T: Scalar>(vertices: &[Point<T>], removed: &[bool], a: usize, b: usize, c: usize) -> bool {
+    let (pa, pb, pc) = (vertices[a], vertices[b], vertices[c]);
+    if is_left(pa, pb, pc) <= 0.0 {
+        return false;
+    }
+    // Vertices already cut off must be skipped: a flat one lies on the edge that replaced it
+    !vertices.iter().enumerate().any(|(i, &p)| {
+        !removed[i] && i != a && i != b && i != c && p != pa && p != pc && is_left(pa, pb, p) >= 0.0 && is_left(pb, pc, p) >= 0.0 && is_left(pc, pa, p) >= 0.0
+    })
+}
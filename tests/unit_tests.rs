@@ -1,6 +1,10 @@
 
-use polyrust::{check_polygon_is_convex, generate_svg_from_polygons, intersect_convex_polygons, ConvexPolygon, Point, Segment};
-use polyrust::intersect_line_segments;
+use polyrust::{check_polygon_is_convex, generate_svg_from_polygons, intersect_convex_polygons, ConvexPolygon, Point, PolyError, Segment};
+use polyrust::{generic, orient2d, ConvexIntersection, orientation, try_intersect_convex_polygons, Line, Orientation};
+use polyrust::{intersect_line_segments, intersect_line_segments_with_tolerance, BooleanOp, MultiPolygon, Polygon, PolygonWithHoles, Tolerance};
+use polyrust::{generate_svg_from_multipolygons, Aabb, Affine, Clip, Hit, HullBuilder, Ray};
+use polyrust::{epa_penetration, gjk_closest_points, gjk_distance, JoinStyle, PointLocation, SegmentIntersection, Support};
+use polyrust::{incircle, Mesh};
 
 
 #[test]
@@ -75,6 +79,8 @@ fn test_point_on_vertex() {
     let polygon = ConvexPolygon::new(&vertices);
     let point = Point { x: 0.0, y: 0.0 };
     assert!(polygon.is_point_inside(point));
+    assert_eq!(polygon.classify_point(point), PointLocation::OnVertex(0));
+    assert_eq!(polygon.classify_point(Point { x: 1.0, y: 2.0 }), PointLocation::OnVertex(2));
 }
 
 #[test]
@@ -87,6 +93,10 @@ fn test_point_on_edge() {
     let polygon = ConvexPolygon::new(&vertices);
     let point = Point { x: 1.0, y: 0.0 };
     assert!(polygon.is_point_inside(point));
+    assert_eq!(polygon.classify_point(point), PointLocation::OnEdge(0));
+    assert_eq!(polygon.classify_point(Point { x: 0.5, y: 1.0 }), PointLocation::OnEdge(2));
+    assert_eq!(polygon.classify_point(Point { x: 1.0, y: 1.0 }), PointLocation::Inside);
+    assert_eq!(polygon.classify_point(Point { x: 1.0, y: -0.01 }), PointLocation::Outside);
 }
 
 #[test]
@@ -118,6 +128,75 @@ fn test_non_convex_polygon() {
 }
 
 
+#[test]
+fn test_try_new_reports_concave_vertex() {
+    let vertices = vec![
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 2.0, y: 0.0 },
+        Point { x: 1.0, y: 1.0 },
+        Point { x: 2.0, y: 2.0 },
+        Point { x: 0.0, y: 2.0 },
+    ];
+    assert_eq!(ConvexPolygon::try_new(&vertices), Err(PolyError::NotConvex { index: 2 }));
+}
+
+#[test]
+fn test_try_new_too_few_vertices() {
+    let vertices = vec![
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 2.0, y: 0.0 },
+    ];
+    assert_eq!(ConvexPolygon::try_new(&vertices), Err(PolyError::TooFewVertices { count: 2 }));
+    assert_eq!(ConvexPolygon::try_new(&[]), Err(PolyError::TooFewVertices { count: 0 }));
+}
+
+#[test]
+fn test_try_new_non_finite() {
+    let vertices = vec![
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 2.0, y: 0.0 },
+        Point { x: f32::NAN, y: 2.0 },
+    ];
+    assert_eq!(ConvexPolygon::try_new(&vertices), Err(PolyError::NonFinite { index: 2 }));
+}
+
+#[test]
+fn test_try_new_collinear_is_degenerate() {
+    let vertices = vec![
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 1.0, y: 1.0 },
+        Point { x: 2.0, y: 2.0 },
+    ];
+    assert_eq!(ConvexPolygon::try_new(&vertices), Err(PolyError::Degenerate));
+}
+
+#[test]
+fn test_try_intersect_with_segment_rejects_non_convex_vertices() {
+    // built directly through the public field, bypassing validation
+    let polygon = ConvexPolygon {
+        vertices: vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 4.0, y: 0.0 },
+            Point { x: 2.0, y: 1.0 },
+            Point { x: 4.0, y: 2.0 },
+            Point { x: 0.0, y: 2.0 },
+        ],
+    };
+    let segment = Segment::new(
+        Point { x: 3.0, y: -1.0 },
+        Point { x: 3.0, y: 3.0 },
+    );
+    assert_eq!(polygon.try_intersect_with_segment(&segment), Err(PolyError::TooManyIntersections { count: 4 }));
+
+    let square = ConvexPolygon::new(&[
+        Point { x: 3.0, y: -1.0 },
+        Point { x: 5.0, y: -1.0 },
+        Point { x: 5.0, y: 3.0 },
+        Point { x: 3.0, y: 3.0 },
+    ]);
+    assert_eq!(try_intersect_convex_polygons(&square, &polygon), Err(PolyError::TooManyIntersections { count: 4 }));
+}
+
 #[test]
 fn test_segment_intersects_triangle() {
     let vertices = vec![
@@ -220,6 +299,7 @@ fn test_intersect_convex_polygons_no_intersection() {
 }
 
 #[test]
+#[allow(clippy::useless_vec, clippy::iter_nth_zero)]
 fn test_intersect_convex_polygons_overlap() {
     let vertices1 = vec![
         Point { x: 0.0, y: 0.0 },
@@ -281,19 +361,98 @@ fn test_intersect_convex_polygons_vertex_touch() {
     let polygon2 = ConvexPolygon::new(&vertices2);
     
     let intersection = intersect_convex_polygons(&polygon1, &polygon2);
-    let inter_poly = ConvexPolygon::new(&intersection);
-    let svg = generate_svg_from_polygons(&vec![&polygon1, &polygon2, &inter_poly],
-        &vec!["blue", "red", "green"],
+    let svg = generate_svg_from_polygons(&vec![&polygon1, &polygon2],
+        &vec!["blue", "red"],
         300, 200, None);
     println!("{}", svg); // for debugging. Open the SVG file in a browser to see the polygons
 
-    println!("inter_poly: {:?}", &inter_poly);
+    println!("intersection: {:?}", &intersection);
 
+    // the polygons only share a vertex, so every point found is that vertex,
+    // which is too degenerate to build a polygon from.
     assert!(!intersection.is_empty());
-    let pts = &inter_poly.vertices;
-    assert_eq!(pts.len(), 1);
-    assert_eq!(pts[0].x, 1.0);
-    assert_eq!(pts[0].y, 2.0);
+    assert!(intersection.iter().all(|p| p.x == 1.0 && p.y == 2.0));
+    assert_eq!(ConvexPolygon::try_new(&intersection), Err(PolyError::Degenerate));
+
+    assert_eq!(polygon1.intersection(&polygon2), ConvexIntersection::Point(Point { x: 1.0, y: 2.0 }));
+    assert_eq!(polygon2.intersection(&polygon1), ConvexIntersection::Point(Point { x: 1.0, y: 2.0 }));
+}
+
+#[test]
+fn test_convex_intersection_shared_edge() {
+    let left = ConvexPolygon::new(&[
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 1.0, y: 0.0 },
+        Point { x: 1.0, y: 1.0 },
+        Point { x: 0.0, y: 1.0 },
+    ]);
+    let right = ConvexPolygon::new(&[
+        Point { x: 1.0, y: -1.0 },
+        Point { x: 2.0, y: -1.0 },
+        Point { x: 2.0, y: 2.0 },
+        Point { x: 1.0, y: 2.0 },
+    ]);
+    match left.intersection(&right) {
+        ConvexIntersection::Segment(segment) => {
+            let mut ends = [segment.p1, segment.p2];
+            ends.sort_by(|a, b| a.y.partial_cmp(&b.y).unwrap());
+            assert_eq!(ends, [Point { x: 1.0, y: 0.0 }, Point { x: 1.0, y: 1.0 }]);
+        }
+        other => panic!("expected a segment, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_convex_intersection_polygon() {
+    let vertices1 = vec![
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 2.0, y: 0.0 },
+        Point { x: 1.0, y: 2.0 },
+    ];
+    let vertices2 = vec![
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 4.0, y: 0.0 },
+        Point { x: 5.0, y: 2.0 },
+    ];
+    let polygon1 = ConvexPolygon::new(&vertices1);
+    let polygon2 = ConvexPolygon::new(&vertices2);
+
+    let inter_poly = polygon1.intersection(&polygon2).into_polygon().unwrap();
+    assert_eq!(inter_poly.vertices, vec![
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 2.0, y: 0.0 },
+        Point { x: 1.6666666, y: 0.6666667 },
+    ]);
+    assert!(check_polygon_is_convex(&inter_poly.vertices));
+
+    // the same region whichever polygon comes first
+    assert_eq!(polygon2.intersection(&polygon1).into_polygon(), Some(inter_poly));
+}
+
+#[test]
+fn test_convex_intersection_contained_and_disjoint() {
+    let outer = ConvexPolygon::new(&[
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 4.0, y: 0.0 },
+        Point { x: 4.0, y: 4.0 },
+        Point { x: 0.0, y: 4.0 },
+    ]);
+    let inner = ConvexPolygon::new(&[
+        Point { x: 1.0, y: 1.0 },
+        Point { x: 2.0, y: 1.0 },
+        Point { x: 1.0, y: 2.0 },
+    ]);
+    assert_eq!(outer.intersection(&inner), ConvexIntersection::Polygon(inner.clone()));
+    assert_eq!(outer.intersection(&outer), ConvexIntersection::Polygon(outer.clone()));
+
+    let far = ConvexPolygon::new(&[
+        Point { x: 10.0, y: 10.0 },
+        Point { x: 11.0, y: 10.0 },
+        Point { x: 10.0, y: 11.0 },
+    ]);
+    let empty = outer.intersection(&far);
+    assert!(empty.is_empty());
+    assert_eq!(empty.into_polygon(), None);
 }
 
 #[test]
@@ -405,43 +564,2089 @@ fn test_generate_svg_from_polygons_auto_viewbox() {
 }
 
 
-// #[test]
-// fn test_intersection_large_non_convex_polygon() {
-//     let vertices1 = vec![
-//         Point { x: 50.0, y: 150.0 },
-//         Point { x: 200.0, y: 50.0 },
-//         Point { x: 350.0, y: 150.0 },
-//         Point { x: 350.0, y: 300.0 },
-//         Point { x: 250.0, y: 300.0 },
-//         Point { x: 200.0, y: 250.0 },
-//         Point { x: 150.0, y: 350.0 },
-//         Point { x: 100.0, y: 250.0 },
-//         Point { x: 100.0, y: 200.0 },    ];
-//     let vertices2 = vec![
-//         Point { x: 100.0, y: 100.0 },
-//         Point { x: 300.0, y: 100.0 },
-//         Point { x: 300.0, y: 300.0 },
-//         Point { x: 100.0, y: 300.0 },   
-//     ];
-//     let expected_intersection = vec![
-//         Point { x: 100.00000, y: 116.66667 },
-//         Point { x: 125.00000, y: 100.00000 },
-//         Point { x: 275.00000, y: 100.00000 },
-//         Point { x: 300.00000, y: 116.66667 },
-//         Point { x: 300.00000, y: 300.00000 },
-//         Point { x: 250.00000, y: 300.00000 },
-//         Point { x: 200.00000, y: 250.00000 },
-//         Point { x: 175.00000, y: 300.00000 },
-//         Point { x: 125.00000, y: 300.00000 },
-//         Point { x: 100.00000, y: 250.00000 }    ];
-//     let polygon1 = ConvexPolygon::new(&vertices1);
-//     let polygon2 = ConvexPolygon::new(&vertices2);
-
-//     let intersection = intersect_convex_polygons(&polygon1, &polygon2);
-//     assert!(!intersection.is_empty());
-//     assert_eq!(intersection.len(), expected_intersection.len());
-//     for i in 0..intersection.len() {
-//         assert!((intersection[i].x - expected_intersection[i].x).abs() < 1e-5);
-//         assert!((intersection[i].y - expected_intersection[i].y).abs() < 1e-5);    
-//     }
-// }
+#[test]
+fn test_f64_polygons_keep_survey_scale_precision() {
+    let square = |x: f64, y: f64| {
+        generic::ConvexPolygon::new(&[
+            generic::Point::new(x, y),
+            generic::Point::new(x + 2.0, y),
+            generic::Point::new(x + 2.0, y + 2.0),
+            generic::Point::new(x, y + 2.0),
+        ])
+    };
+    let polygon1 = square(512345.1, 401234.7);
+    let polygon2 = square(512346.1, 401235.7);
+
+    let intersection = generic::ConvexPolygon::new(&intersect_convex_polygons(&polygon1, &polygon2));
+    let expected = [
+        (512346.1, 401235.7),
+        (512347.1, 401235.7),
+        (512347.1, 401236.7),
+        (512346.1, 401236.7),
+    ];
+    for (x, y) in expected {
+        assert!(intersection.vertices.iter().any(|p| (p.x - x).abs() < 1e-6 && (p.y - y).abs() < 1e-6));
+    }
+
+    // the same shapes in f32 can't even represent the input coordinates that closely
+    let narrowed: ConvexPolygon = polygon1.cast();
+    assert!((narrowed.vertices[0].x as f64 - polygon1.vertices[0].x).abs() > 1e-3);
+}
+
+#[test]
+fn test_scalar_conversions() {
+    let p = Point { x: 1.5, y: -2.25 };
+    let wide: generic::Point<f64> = p.into();
+    assert_eq!(wide, generic::Point::new(1.5, -2.25));
+    assert_eq!(wide.cast::<f32>(), p);
+
+    let segment = Segment::new(p, Point { x: 0.1, y: 0.2 });
+    let wide_segment: generic::Segment<f64> = segment.into();
+    assert_eq!(wide_segment.p2.x, 0.1f32 as f64);
+    assert_eq!(wide_segment.cast::<f32>(), segment);
+
+    let line: generic::Line<f64> = Line::from_points(p, Point { x: 0.0, y: 0.0 }).into();
+    assert_eq!(line, generic::Line::new(2.25, 1.5, 0.0));
+}
+
+#[test]
+fn test_generate_svg_from_f64_polygons() {
+    let polygon1 = generic::ConvexPolygon::new(&[
+        generic::Point::new(0.0, 0.0),
+        generic::Point::new(2.0, 0.0),
+        generic::Point::new(2.0, 2.0),
+        generic::Point::new(0.0, 2.0),
+    ]);
+    let polygon2 = generic::ConvexPolygon::new(&[
+        generic::Point::new(1.0, 1.0),
+        generic::Point::new(3.0, 1.0),
+        generic::Point::new(3.0, 3.0),
+    ]);
+    let pad = 0.1;
+    let svg = generate_svg_from_polygons(&vec![&polygon1, &polygon2],
+        &vec!["red", "blue"],
+        100, 100,
+        Some((generic::Point::new(0.0 - pad, 0.0 - pad), generic::Point::new(3.0 + pad, 3.0 + pad)))
+    );
+    let header = r#"<svg width="100" height="100" xmlns="http://www.w3.org/2000/svg" viewBox="-0.1 -0.1 3.2 3.2">"#;
+    assert!(svg.starts_with(header));
+}
+
+
+/// Exact orientation for coordinates in [0.5, 32), which are all multiples of 2^-53,
+/// so scaling by 2^53 turns them into integers small enough for i128 products.
+fn exact_orientation(pa: (f64, f64), pb: (f64, f64), pc: (f64, f64)) -> Orientation {
+    let s = |v: f64| (v * 2f64.powi(53)) as i128;
+    let det = (s(pa.0) - s(pc.0)) * (s(pb.1) - s(pc.1)) - (s(pa.1) - s(pc.1)) * (s(pb.0) - s(pc.0));
+    match det.signum() {
+        1 => Orientation::CounterClockwise,
+        -1 => Orientation::Clockwise,
+        _ => Orientation::Collinear,
+    }
+}
+
+// Near-collinear configurations from Kettner et al., "Classroom examples of robustness
+// problems in geometric computations": a grid of points one ulp apart around (0.5, 0.5)
+// tested against lines that pass (almost) through it.
+#[allow(clippy::excessive_precision)]
+const NEAR_DEGENERATE_LINES: [((f64, f64), (f64, f64)); 3] = [
+    ((12.0, 12.0), (24.0, 24.0)),
+    ((17.300000000000001, 17.300000000000001), (24.00000000000005, 24.0000000000000053)),
+    ((8.8000000000000007, 8.8000000000000007), (12.1, 12.1)),
+];
+
+#[test]
+fn test_orient2d_near_degenerate_corpus() {
+    let ulp = 2f64.powi(-53);
+    let mut naive_wrong = 0;
+    for (q, r) in NEAR_DEGENERATE_LINES {
+        for i in 0..256 {
+            for j in 0..256 {
+                let p = (0.5 + i as f64 * ulp, 0.5 + j as f64 * ulp);
+                let expected = exact_orientation(q, r, p);
+                let pq = generic::Point::new(q.0, q.1);
+                let pr = generic::Point::new(r.0, r.1);
+                let pp = generic::Point::new(p.0, p.1);
+                assert_eq!(orientation(pq, pr, pp), expected, "q={q:?} r={r:?} p={p:?}");
+
+                let naive = (r.0 - q.0) * (p.1 - q.1) - (p.0 - q.0) * (r.1 - q.1);
+                let naive_sign = if naive > 0.0 {
+                    Orientation::CounterClockwise
+                } else if naive < 0.0 {
+                    Orientation::Clockwise
+                } else {
+                    Orientation::Collinear
+                };
+                if naive_sign != expected {
+                    naive_wrong += 1;
+                }
+            }
+        }
+    }
+    // the corpus is only useful if plain floating point gets it wrong
+    assert!(naive_wrong > 0);
+}
+
+#[test]
+fn test_orient2d_f32_points() {
+    let (a, b, c) = (1.1f32, 3.3f32, 2.2f32);
+    let expected = exact_orientation((a as f64, a as f64), (b as f64, b as f64), (c as f64, c as f64));
+    assert_eq!(orientation(Point { x: a, y: a }, Point { x: b, y: b }, Point { x: c, y: c }), expected);
+    assert_eq!(orientation(Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 0.0 }, Point { x: 0.5, y: 1e-30 }), Orientation::CounterClockwise);
+    assert_eq!(orientation(Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 0.0 }, Point { x: 0.5, y: -1e-30 }), Orientation::Clockwise);
+    assert_eq!(orient2d(Point { x: 0.0, y: 0.0 }, Point { x: 2.0, y: 0.0 }, Point { x: 0.0, y: 3.0 }), 6.0);
+}
+
+#[test]
+fn test_near_collinear_point_inside_matches_orientation() {
+    // a point one ulp either side of the long edge (12,12)-(24,24) of this triangle
+    let triangle = generic::ConvexPolygon::new(&[
+        generic::Point::new(12.0, 12.0),
+        generic::Point::new(24.0, 24.0),
+        generic::Point::new(0.0, 24.0),
+    ]);
+    let ulp = 2f64.powi(-48);
+    for i in 0..32 {
+        for j in 0..32 {
+            let p = generic::Point::new(18.0 + i as f64 * ulp, 18.0 + j as f64 * ulp);
+            if i != j {
+                assert_eq!(triangle.is_point_inside(p), j > i, "p={p:?}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_near_collinear_convexity_matches_orientation() {
+    let ulp = 2f64.powi(-48);
+    for i in 0..32 {
+        for j in 0..32 {
+            let p = generic::Point::new(18.0 + i as f64 * ulp, 18.0 + j as f64 * ulp);
+            let vertices = [
+                generic::Point::new(12.0, 12.0),
+                p,
+                generic::Point::new(24.0, 24.0),
+                generic::Point::new(0.0, 24.0),
+            ];
+            assert_eq!(check_polygon_is_convex(&vertices), j <= i, "p={p:?}");
+        }
+    }
+}
+
+#[test]
+fn test_near_collinear_segment_intersection_matches_orientation() {
+    let (q, r) = NEAR_DEGENERATE_LINES[1];
+    let seg_one = generic::Segment::new(generic::Point::new(q.0, q.1), generic::Point::new(r.0, r.1));
+    let ulp = 2f64.powi(-48);
+    for i in 0..16 {
+        for j in 0..16 {
+            let a = (20.0 + i as f64 * ulp, 20.0 - 8.0 * ulp + j as f64 * ulp);
+            let b = (20.0 + j as f64 * ulp, 20.0 - 8.0 * ulp + i as f64 * ulp);
+            if a == b {
+                continue;
+            }
+            let seg_two = generic::Segment::new(generic::Point::new(a.0, a.1), generic::Point::new(b.0, b.1));
+            let side_a = exact_orientation(q, r, a);
+            let side_b = exact_orientation(q, r, b);
+            let expected = side_a != side_b || side_a == Orientation::Collinear;
+
+            let intersection = intersect_line_segments(&seg_one, &seg_two);
+            assert_eq!(intersection.is_some(), expected, "a={a:?} b={b:?}");
+            if let Some(p) = intersection {
+                assert!(p.x >= a.0.min(b.0) && p.x <= a.0.max(b.0));
+                assert!(p.y >= a.1.min(b.1) && p.y <= a.1.max(b.1));
+            }
+        }
+    }
+}
+
+
+#[test]
+fn test_tolerance_scales_with_coordinates() {
+    let tolerance = Tolerance::default();
+    assert!(tolerance.eq(1_000_000.0, 1_000_000.25));
+    assert!(!tolerance.eq(1_000_000.0, 1_000_001.0));
+    assert!(tolerance.eq(1e-6, 1e-6 + 1e-13));
+    assert!(!tolerance.eq(1e-6, 1.00001e-6));
+    assert!(tolerance.points_eq(Point { x: 0.0, y: 1e6 }, Point { x: 0.25, y: 1e6 }));
+
+    let exact = Tolerance::exact();
+    assert!(exact.eq(1.5, 1.5));
+    assert!(!exact.eq(1.5, 1.5 + f32::EPSILON));
+    assert_eq!(Tolerance::new(0.5, 0.25).epsilon_at(-4.0), 1.5);
+}
+
+#[test]
+fn test_try_new_merges_vertices_within_tolerance() {
+    let vertices = vec![
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 1.0, y: 0.0 },
+        Point { x: 1.0, y: 1.0 },
+        Point { x: 0.9999999, y: 1.0 },
+        Point { x: 0.0, y: 1.0 },
+    ];
+    assert_eq!(ConvexPolygon::new(&vertices).vertices.len(), 4);
+    let exact = ConvexPolygon::try_new_with_tolerance(&vertices, &Tolerance::exact()).unwrap();
+    assert_eq!(exact.vertices.len(), 5);
+}
+
+#[test]
+fn test_try_new_merges_before_checking_convexity() {
+    // the near-duplicate at (1 + 1e-9, 1) turns clockwise until it is merged away
+    let p = |x, y| generic::Point::new(x, y);
+    let vertices = [p(0.0, 0.0), p(1.0, 0.0), p(1.0, 1.0), p(1.0 + 1e-9, 1.0), p(0.0, 1.0)];
+    let tolerance = generic::Tolerance::new(1e-6, 0.0);
+    assert_eq!(generic::ConvexPolygon::try_new_with_tolerance(&vertices, &tolerance).unwrap().vertices.len(), 4);
+    assert_eq!(generic::ConvexPolygon::try_new_with_tolerance(&vertices, &generic::Tolerance::exact()), Err(PolyError::NotConvex { index: 2 }));
+
+    // a concave vertex is reported by its index in the input
+    let vertices = [p(0.0, 0.0), p(1e-9, 0.0), p(2.0, 0.0), p(1.0, 1.0), p(2.0, 2.0), p(0.0, 2.0)];
+    assert_eq!(generic::ConvexPolygon::try_new_with_tolerance(&vertices, &tolerance), Err(PolyError::NotConvex { index: 3 }));
+}
+
+#[test]
+fn test_point_inside_with_tolerance_at_large_offsets() {
+    let square = generic::ConvexPolygon::new(&[
+        generic::Point::new(100000.0, 100000.0),
+        generic::Point::new(100001.0, 100000.0),
+        generic::Point::new(100001.0, 100001.0),
+        generic::Point::new(100000.0, 100001.0),
+    ]);
+    // next to a corner the point snaps onto it
+    let near_corner = generic::Point::new(100001.0 + 1e-11, 100000.0);
+    assert!(square.is_point_inside(near_corner));
+    assert!(!square.is_point_inside_with_tolerance(near_corner, &generic::Tolerance::exact()));
+    assert!(!square.is_point_inside(generic::Point::new(100001.0 + 1e-6, 100000.0)));
+
+    // next to the middle of an edge which side it is on is decided exactly
+    assert!(!square.is_point_inside(generic::Point::new(100001.0 + 1e-10, 100000.5)));
+    assert!(square.is_point_inside(generic::Point::new(100001.0 - 1e-10, 100000.5)));
+}
+
+#[test]
+fn test_segments_touching_within_tolerance() {
+    let seg_one = generic::Segment::new(generic::Point::new(100000.0, 0.0), generic::Point::new(100000.0, 10.0));
+    let seg_two = generic::Segment::new(generic::Point::new(90000.0, 0.0), generic::Point::new(100000.0 - 1e-11, 0.0));
+    assert_eq!(intersect_line_segments(&seg_one, &seg_two), Some(seg_two.p2));
+    assert_eq!(intersect_line_segments_with_tolerance(&seg_one, &seg_two, &generic::Tolerance::exact()), None);
+
+    // stopping just short of the middle of seg_one is a miss however close it gets
+    let seg_three = generic::Segment::new(generic::Point::new(90000.0, 5.0), generic::Point::new(100000.0 - 1e-10, 5.0));
+    assert_eq!(intersect_line_segments(&seg_one, &seg_three), None);
+}
+
+#[test]
+fn test_near_parallel_segments_stay_on_segments() {
+    // almost parallel: the crossing must not be pushed off either segment by rounding
+    let seg_one = Segment::new(Point { x: 0.0, y: 0.0 }, Point { x: 1000.0, y: 0.001 });
+    let seg_two = Segment::new(Point { x: 0.0, y: 0.0005 }, Point { x: 1000.0, y: 0.0 });
+    let p = intersect_line_segments_with_tolerance(&seg_one, &seg_two, &Tolerance::exact()).unwrap();
+    assert!(p.x >= 0.0 && p.x <= 1000.0);
+    assert!(p.y >= 0.0 && p.y <= 0.001);
+    assert!((p.x - 333.33334).abs() < 1.0);
+
+    // the tolerance only snaps end points, so the default finds the same crossing
+    let q = intersect_line_segments(&seg_one, &seg_two).unwrap();
+    assert!((q.x - 333.33334).abs() < 1.0, "{q:?}");
+}
+
+
+/// Small deterministic generator so the randomized tests are reproducible.
+struct Lcg(u64);
+
+impl Lcg {
+    fn next_f64(&mut self) -> f64 {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (self.0 >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn next_below(&mut self, n: usize) -> usize {
+        (self.next_f64() * n as f64) as usize
+    }
+}
+
+/// A discretized circle, the kind of high-vertex-count input the chasing algorithm is for.
+fn circle_polygon(cx: f64, cy: f64, r: f64, n: usize, phase: f64) -> generic::ConvexPolygon<f64> {
+    let vertices: Vec<_> = (0..n)
+        .map(|i| {
+            let angle = phase + 2.0 * std::f64::consts::PI * i as f64 / n as f64;
+            generic::Point::new(cx + r * angle.cos(), cy + r * angle.sin())
+        })
+        .collect();
+    generic::ConvexPolygon::new(&vertices)
+}
+
+/// Integer-coordinate shapes that share vertices and edges with each other all the time.
+fn grid_polygon(rng: &mut Lcg) -> generic::ConvexPolygon<f64> {
+    let x0 = rng.next_below(4) as f64;
+    let y0 = rng.next_below(4) as f64;
+    let w = 1.0 + rng.next_below(3) as f64;
+    let h = 1.0 + rng.next_below(3) as f64;
+    let p = generic::Point::new;
+    let vertices = match rng.next_below(4) {
+        0 => vec![p(x0, y0), p(x0 + w, y0), p(x0 + w, y0 + h), p(x0, y0 + h)],
+        1 => vec![p(x0, y0), p(x0 + w, y0), p(x0, y0 + h)],
+        2 => vec![p(x0, y0), p(x0 + w, y0 + h), p(x0, y0 + h)],
+        _ => vec![p(x0 + w, y0), p(x0 + 2.0 * w, y0 + h), p(x0 + w, y0 + 2.0 * h), p(x0, y0 + h)],
+    };
+    generic::ConvexPolygon::new(&vertices)
+}
+
+/// Checks the chasing result against the boundary points found by testing every pair of edges.
+fn assert_matches_brute_force(poly_one: &generic::ConvexPolygon<f64>, poly_two: &generic::ConvexPolygon<f64>) {
+    let tolerance = generic::Tolerance::default();
+    let mut brute: Vec<generic::Point<f64>> = vec![];
+    for p in intersect_convex_polygons(poly_one, poly_two) {
+        if !brute.iter().any(|q| tolerance.points_eq(p, *q)) {
+            brute.push(p);
+        }
+    }
+    let fast = poly_one.intersection(poly_two);
+    let context = format!("{poly_one:?} {poly_two:?} gave {fast:?}, brute force found {brute:?}");
+    let near = |a: generic::Point<f64>, b: generic::Point<f64>| a.distance(b) < 1e-9;
+    match &fast {
+        generic::ConvexIntersection::Empty => assert!(brute.is_empty(), "{context}"),
+        generic::ConvexIntersection::Point(p) => {
+            assert!(!brute.is_empty() && brute.iter().all(|q| near(*p, *q)), "{context}")
+        }
+        generic::ConvexIntersection::Segment(s) => {
+            assert!(brute.iter().any(|q| near(s.p1, *q)) && brute.iter().any(|q| near(s.p2, *q)), "{context}");
+            assert!(brute.iter().all(|q| s.distance_to_point(*q) < 1e-9), "{context}");
+        }
+        generic::ConvexIntersection::Polygon(polygon) => {
+            assert!(check_polygon_is_convex(&polygon.vertices), "{context}");
+            assert!(polygon.vertices.iter().all(|v| brute.iter().any(|q| near(*v, *q))), "{context}");
+            assert!(brute.iter().all(|q| polygon.is_point_inside(*q)), "{context}");
+        }
+    }
+}
+
+#[test]
+fn test_chasing_intersection_matches_brute_force_for_circles() {
+    let mut rng = Lcg(0x5eed);
+    for _ in 0..300 {
+        let one = circle_polygon(rng.next_f64() * 4.0, rng.next_f64() * 4.0, 0.5 + rng.next_f64() * 3.0, 3 + rng.next_below(60), rng.next_f64());
+        let two = circle_polygon(rng.next_f64() * 4.0, rng.next_f64() * 4.0, 0.5 + rng.next_f64() * 3.0, 3 + rng.next_below(60), rng.next_f64());
+        assert_matches_brute_force(&one, &two);
+        assert_matches_brute_force(&two, &one);
+    }
+}
+
+#[test]
+fn test_chasing_intersection_matches_brute_force_for_touching_shapes() {
+    let mut rng = Lcg(42);
+    for _ in 0..2000 {
+        let one = grid_polygon(&mut rng);
+        let two = grid_polygon(&mut rng);
+        assert_matches_brute_force(&one, &two);
+        assert_matches_brute_force(&two, &one);
+    }
+}
+
+
+#[test]
+fn test_chasing_intersection_of_large_circles() {
+    let one = circle_polygon(0.0, 0.0, 10.0, 3000, 0.0);
+    let two = circle_polygon(5.0, 0.0, 10.0, 3000, 0.1);
+    let lens = one.intersection(&two).into_polygon().unwrap();
+    assert!(check_polygon_is_convex(&lens.vertices));
+    // each circle has the arc within 75.5 degrees of the other's centre inside it
+    assert!(lens.vertices.len() > 2400 && lens.vertices.len() < 2600);
+    for v in lens.vertices.iter().step_by(100) {
+        assert!(one.is_point_inside(*v) && two.is_point_inside(*v));
+    }
+}
+
+
+fn l_shape() -> Vec<Point> {
+    vec![
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 4.0, y: 0.0 },
+        Point { x: 4.0, y: 2.0 },
+        Point { x: 2.0, y: 2.0 },
+        Point { x: 2.0, y: 4.0 },
+        Point { x: 0.0, y: 4.0 },
+    ]
+}
+
+#[test]
+fn test_polygon_keeps_vertex_order() {
+    let vertices = l_shape();
+    assert_eq!(Polygon::new(&vertices).vertices, vertices);
+    let reversed: Vec<Point> = vertices.iter().rev().copied().collect();
+    assert_eq!(Polygon::new(&reversed).vertices, reversed);
+}
+
+#[test]
+fn test_polygon_area_and_perimeter() {
+    let polygon = Polygon::new(&l_shape());
+    assert_eq!(polygon.area(), 12.0);
+    assert_eq!(polygon.perimeter(), 16.0);
+    let reversed: Vec<Point> = l_shape().into_iter().rev().collect();
+    assert_eq!(Polygon::new(&reversed).area(), 12.0);
+}
+
+#[test]
+fn test_polygon_point_inside_concave() {
+    let polygon = Polygon::new(&l_shape());
+    assert!(polygon.is_point_inside(Point { x: 1.0, y: 3.0 }));
+    assert!(polygon.is_point_inside(Point { x: 3.0, y: 1.0 }));
+    assert!(polygon.is_point_inside(Point { x: 2.0, y: 3.0 })); // on the inner edge
+    assert!(!polygon.is_point_inside(Point { x: 3.0, y: 3.0 })); // in the notch
+    assert!(!polygon.is_point_inside(Point { x: -1.0, y: 1.0 }));
+}
+
+#[test]
+fn test_polygon_segment_crosses_notch() {
+    let polygon = Polygon::new(&l_shape());
+    let segment = Segment::new(Point { x: 1.0, y: 3.0 }, Point { x: 3.0, y: 1.0 });
+    assert_eq!(
+        polygon.intersect_with_segment(&segment),
+        vec![Point { x: 2.0, y: 2.0 }]
+    );
+    let segment = Segment::new(Point { x: -1.0, y: 3.0 }, Point { x: 5.0, y: 3.0 });
+    assert_eq!(
+        polygon.intersect_with_segment(&segment),
+        vec![Point { x: 0.0, y: 3.0 }, Point { x: 2.0, y: 3.0 }]
+    );
+    let segment = Segment::new(Point { x: 5.0, y: 1.0 }, Point { x: -1.0, y: 1.0 });
+    assert_eq!(
+        polygon.intersect_with_segment(&segment),
+        vec![Point { x: 4.0, y: 1.0 }, Point { x: 0.0, y: 1.0 }]
+    );
+}
+
+#[test]
+fn test_polygon_rejects_self_intersection() {
+    let bowtie = vec![
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 2.0, y: 2.0 },
+        Point { x: 2.0, y: 0.0 },
+        Point { x: 0.0, y: 2.0 },
+    ];
+    assert_eq!(Polygon::try_new(&bowtie), Err(PolyError::SelfIntersecting { first: 0, second: 2 }));
+
+    // a vertex touching a non-adjacent edge
+    let touching = vec![
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 4.0, y: 0.0 },
+        Point { x: 4.0, y: 4.0 },
+        Point { x: 2.0, y: 0.0 },
+        Point { x: 0.0, y: 4.0 },
+    ];
+    assert_eq!(Polygon::try_new(&touching), Err(PolyError::SelfIntersecting { first: 0, second: 2 }));
+
+    // an edge doubling back along the previous one
+    let spike = vec![
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 4.0, y: 0.0 },
+        Point { x: 2.0, y: 0.0 },
+        Point { x: 2.0, y: 2.0 },
+    ];
+    assert_eq!(Polygon::try_new(&spike), Err(PolyError::SelfIntersecting { first: 0, second: 1 }));
+}
+
+#[test]
+fn test_polygon_try_new_errors() {
+    assert_eq!(Polygon::try_new(&l_shape()[..2]), Err(PolyError::TooFewVertices { count: 2 }));
+    let mut vertices = l_shape();
+    vertices[3].y = f32::INFINITY;
+    assert_eq!(Polygon::try_new(&vertices), Err(PolyError::NonFinite { index: 3 }));
+    let collinear = vec![Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 1.0 }, Point { x: 3.0, y: 3.0 }];
+    assert_eq!(Polygon::try_new(&collinear), Err(PolyError::Degenerate));
+}
+
+#[test]
+fn test_polygon_merges_repeated_vertices() {
+    let mut vertices = l_shape();
+    vertices.insert(2, vertices[1]);
+    vertices.push(vertices[0]);
+    assert_eq!(Polygon::new(&vertices).vertices, l_shape());
+}
+
+#[test]
+fn test_polygon_f64_area_far_from_origin() {
+    let offset = 1.0e7;
+    let vertices: Vec<generic::Point<f64>> = l_shape()
+        .iter()
+        .map(|p| generic::Point::new(p.x as f64 + offset, p.y as f64 + offset))
+        .collect();
+    let polygon = generic::Polygon::new(&vertices);
+    assert_eq!(polygon.area(), 12.0);
+    assert!(polygon.is_point_inside(generic::Point::new(offset + 1.0, offset + 3.0)));
+    assert!(!polygon.is_point_inside(generic::Point::new(offset + 3.0, offset + 3.0)));
+}
+
+#[test]
+fn test_intersection_large_non_convex_polygon() {
+    let vertices1 = vec![
+        Point { x: 50.0, y: 150.0 },
+        Point { x: 200.0, y: 50.0 },
+        Point { x: 350.0, y: 150.0 },
+        Point { x: 350.0, y: 300.0 },
+        Point { x: 250.0, y: 300.0 },
+        Point { x: 200.0, y: 250.0 },
+        Point { x: 150.0, y: 350.0 },
+        Point { x: 100.0, y: 250.0 },
+        Point { x: 100.0, y: 200.0 },    ];
+    let vertices2 = vec![
+        Point { x: 100.0, y: 100.0 },
+        Point { x: 300.0, y: 100.0 },
+        Point { x: 300.0, y: 300.0 },
+        Point { x: 100.0, y: 300.0 },
+    ];
+    let expected_intersection = vec![
+        Point { x: 100.00000, y: 116.66667 },
+        Point { x: 125.00000, y: 100.00000 },
+        Point { x: 275.00000, y: 100.00000 },
+        Point { x: 300.00000, y: 116.66667 },
+        Point { x: 300.00000, y: 300.00000 },
+        Point { x: 250.00000, y: 300.00000 },
+        Point { x: 200.00000, y: 250.00000 },
+        Point { x: 175.00000, y: 300.00000 },
+        Point { x: 125.00000, y: 300.00000 },
+        Point { x: 100.00000, y: 250.00000 }    ];
+    let polygon1 = Polygon::new(&vertices1);
+    let polygon2 = Polygon::new(&vertices2);
+
+    let result = polygon1.intersection(&polygon2);
+    assert_eq!(result.polygons.len(), 1);
+    assert!(result.polygons[0].interiors.is_empty());
+    let intersection = &result.polygons[0].exterior.vertices;
+    assert_eq!(intersection.len(), expected_intersection.len());
+    for i in 0..intersection.len() {
+        assert!((intersection[i].x - expected_intersection[i].x).abs() < 1e-4);
+        assert!((intersection[i].y - expected_intersection[i].y).abs() < 1e-4);
+    }
+}
+
+fn square(x: f32, y: f32, size: f32) -> Polygon {
+    Polygon::new(&[
+        Point { x, y },
+        Point { x: x + size, y },
+        Point { x: x + size, y: y + size },
+        Point { x, y: y + size },
+    ])
+}
+
+#[test]
+fn test_boolean_ops_on_overlapping_squares() {
+    let one = square(0.0, 0.0, 2.0);
+    let two = square(1.0, 1.0, 2.0);
+
+    let intersection = one.intersection(&two);
+    assert_eq!(intersection.polygons.len(), 1);
+    assert_eq!(intersection.polygons[0].exterior, square(1.0, 1.0, 1.0));
+
+    let union = one.union(&two);
+    assert_eq!(union.polygons.len(), 1);
+    assert_eq!(union.polygons[0].exterior.vertices.len(), 8);
+    assert_eq!(union.area(), 7.0);
+
+    let difference = one.difference(&two);
+    assert_eq!(
+        difference.polygons[0].exterior.vertices,
+        vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 2.0, y: 0.0 },
+            Point { x: 2.0, y: 1.0 },
+            Point { x: 1.0, y: 1.0 },
+            Point { x: 1.0, y: 2.0 },
+            Point { x: 0.0, y: 2.0 },
+        ]
+    );
+
+    let xor = one.xor(&two);
+    assert_eq!(xor.polygons.len(), 2);
+    assert_eq!(xor.area(), 6.0);
+}
+
+#[test]
+fn test_boolean_difference_makes_hole() {
+    let outer = square(0.0, 0.0, 4.0);
+    let inner = square(1.0, 1.0, 2.0);
+
+    let difference = outer.difference(&inner);
+    assert_eq!(difference.polygons.len(), 1);
+    assert_eq!(difference.polygons[0].exterior, outer);
+    assert_eq!(difference.polygons[0].interiors.len(), 1);
+    // holes run clockwise
+    let hole: Vec<Point> = difference.polygons[0].interiors[0].vertices.clone();
+    assert_eq!(hole, vec![Point { x: 1.0, y: 1.0 }, Point { x: 1.0, y: 3.0 }, Point { x: 3.0, y: 3.0 }, Point { x: 3.0, y: 1.0 }]);
+    assert_eq!(difference.area(), 12.0);
+
+    assert_eq!(outer.intersection(&inner).polygons[0].exterior, inner);
+    assert_eq!(outer.union(&inner).polygons[0].exterior, outer);
+    assert!(inner.difference(&outer).is_empty());
+    assert_eq!(outer.xor(&inner), difference);
+}
+
+#[test]
+fn test_boolean_ops_on_touching_and_identical_squares() {
+    let one = square(0.0, 0.0, 1.0);
+    let beside = square(1.0, 0.0, 1.0);
+    assert!(one.intersection(&beside).is_empty());
+    let union = one.union(&beside);
+    assert_eq!(union.polygons.len(), 1);
+    assert_eq!(union.polygons[0].exterior.vertices.len(), 4);
+    assert_eq!(union.area(), 2.0);
+    assert_eq!(one.difference(&beside).polygons[0].exterior, one);
+
+    // squares meeting at a corner stay separate
+    let corner = square(1.0, 1.0, 1.0);
+    assert_eq!(one.union(&corner).polygons.len(), 2);
+    assert!(one.intersection(&corner).is_empty());
+
+    // the same square, given clockwise
+    let clockwise = Polygon::new(&one.vertices.iter().rev().copied().collect::<Vec<_>>());
+    assert_eq!(one.intersection(&clockwise).polygons[0].exterior, one);
+    assert_eq!(one.union(&clockwise).polygons[0].exterior, one);
+    assert!(one.difference(&clockwise).is_empty());
+    assert!(one.xor(&clockwise).is_empty());
+
+    let far = square(5.0, 5.0, 1.0);
+    assert!(one.intersection(&far).is_empty());
+    assert_eq!(one.union(&far).polygons.len(), 2);
+    assert_eq!(one.boolean_with_tolerance(&far, BooleanOp::Difference, &Tolerance::default()).polygons[0].exterior, one);
+}
+
+/// A random star-shaped polygon, which is always simple.
+fn star_polygon(rng: &mut Lcg, grid: bool) -> Option<generic::Polygon<f64>> {
+    let (cx, cy) = (rng.next_f64() * 4.0, rng.next_f64() * 4.0);
+    let n = 3 + rng.next_below(12);
+    let mut angles: Vec<f64> = (0..n).map(|_| rng.next_f64() * 2.0 * std::f64::consts::PI).collect();
+    angles.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let vertices: Vec<generic::Point<f64>> = angles
+        .iter()
+        .map(|a| {
+            let r = 0.5 + rng.next_f64() * 2.5;
+            let (x, y) = (cx + r * a.cos(), cy + r * a.sin());
+            if grid {
+                generic::Point::new(x.round(), y.round())
+            } else {
+                generic::Point::new(x, y)
+            }
+        })
+        .collect();
+    generic::Polygon::try_new(&vertices).ok()
+}
+
+fn assert_boolean_areas_consistent(one: &generic::Polygon<f64>, two: &generic::Polygon<f64>) {
+    let (a, b) = (one.area(), two.area());
+    let intersection = one.intersection(two).area();
+    let union = one.union(two).area();
+    let difference = one.difference(two).area();
+    let xor = one.xor(two).area();
+    let context = format!("{one:?} {two:?}: {intersection} {union} {difference} {xor}");
+    let close = |x: f64, y: f64| (x - y).abs() < 1e-9 * (1.0 + a + b);
+    assert!(intersection <= a.min(b) + 1e-9, "{context}");
+    assert!(close(union, a + b - intersection), "{context}");
+    assert!(close(difference, a - intersection), "{context}");
+    assert!(close(xor, union - intersection), "{context}");
+}
+
+#[test]
+fn test_boolean_areas_consistent_for_random_polygons() {
+    let mut rng = Lcg(7);
+    for grid in [false, true] {
+        for _ in 0..300 {
+            if let (Some(one), Some(two)) = (star_polygon(&mut rng, grid), star_polygon(&mut rng, grid)) {
+                assert_boolean_areas_consistent(&one, &two);
+                assert_boolean_areas_consistent(&two, &one);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_boolean_intersection_matches_convex_intersection() {
+    let mut rng = Lcg(42);
+    for _ in 0..500 {
+        let one = grid_polygon(&mut rng);
+        let two = grid_polygon(&mut rng);
+        let expected = one.intersection(&two).into_polygon().map_or(0.0, |p| generic::Polygon::new(&p.vertices).area());
+        let result = generic::Polygon::new(&one.vertices).intersection(&generic::Polygon::new(&two.vertices));
+        assert!((result.area() - expected).abs() < 1e-9, "{one:?} {two:?} gave {result:?}");
+    }
+}
+
+fn square_with_hole() -> PolygonWithHoles {
+    // the hole is given counter-clockwise and gets reversed
+    PolygonWithHoles::new(square(0.0, 0.0, 4.0), vec![square(1.0, 1.0, 2.0)])
+}
+
+#[test]
+fn test_polygon_with_holes_orients_rings() {
+    let clockwise = Polygon::new(&square(0.0, 0.0, 4.0).vertices.iter().rev().copied().collect::<Vec<_>>());
+    let polygon = PolygonWithHoles::new(clockwise, vec![square(1.0, 1.0, 2.0)]);
+    assert_eq!(polygon, square_with_hole());
+    assert_eq!(polygon.exterior, square(0.0, 0.0, 4.0));
+    assert_eq!(polygon.interiors[0].vertices[0], Point { x: 1.0, y: 3.0 });
+}
+
+#[test]
+fn test_polygon_with_holes_rejects_bad_holes() {
+    let outer = square(0.0, 0.0, 4.0);
+    assert_eq!(
+        PolygonWithHoles::try_new(outer.clone(), vec![square(3.0, 3.0, 2.0)]),
+        Err(PolyError::HoleOutsideExterior { index: 0 })
+    );
+    assert_eq!(
+        PolygonWithHoles::try_new(outer.clone(), vec![square(1.0, 1.0, 1.0), square(5.0, 5.0, 1.0)]),
+        Err(PolyError::HoleOutsideExterior { index: 1 })
+    );
+    assert_eq!(
+        PolygonWithHoles::try_new(outer.clone(), vec![square(0.5, 0.5, 2.0), square(1.5, 1.5, 2.0)]),
+        Err(PolyError::OverlappingHoles { first: 0, second: 1 })
+    );
+    // holes may touch each other and the exterior at points
+    let diamond = Polygon::new(&[Point { x: 1.0, y: 0.0 }, Point { x: 2.0, y: 1.0 }, Point { x: 1.0, y: 2.0 }, Point { x: 0.0, y: 1.0 }]);
+    let touching = PolygonWithHoles::try_new(outer.clone(), vec![diamond, square(2.0, 1.0, 1.0)]);
+    assert!(touching.is_ok());
+
+    // but not share an edge with the exterior, or cover a notch in it
+    assert_eq!(
+        PolygonWithHoles::try_new(outer.clone(), vec![square(0.0, 1.0, 1.0)]),
+        Err(PolyError::HoleOutsideExterior { index: 0 })
+    );
+    let notched = Polygon::new(&[
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 4.0, y: 0.0 },
+        Point { x: 4.0, y: 4.0 },
+        Point { x: 3.0, y: 4.0 },
+        Point { x: 2.0, y: 2.0 },
+        Point { x: 1.0, y: 4.0 },
+        Point { x: 0.0, y: 4.0 },
+    ]);
+    let over_notch = Polygon::new(&[Point { x: 1.0, y: 4.0 }, Point { x: 2.0, y: 1.0 }, Point { x: 3.0, y: 4.0 }]);
+    assert_eq!(PolygonWithHoles::try_new(notched, vec![over_notch]), Err(PolyError::HoleOutsideExterior { index: 0 }));
+
+    // but not repeat, share an edge or have all their vertices on another's boundary
+    let big = square(0.0, 0.0, 10.0);
+    assert_eq!(
+        PolygonWithHoles::try_new(big.clone(), vec![square(4.0, 4.0, 2.0), square(4.0, 4.0, 2.0)]),
+        Err(PolyError::OverlappingHoles { first: 0, second: 1 })
+    );
+    assert_eq!(
+        PolygonWithHoles::try_new(big.clone(), vec![square(4.0, 4.0, 2.0), square(6.0, 4.0, 2.0)]),
+        Err(PolyError::OverlappingHoles { first: 0, second: 1 })
+    );
+    let diamond = Polygon::new(&[Point { x: 5.0, y: 4.0 }, Point { x: 6.0, y: 5.0 }, Point { x: 5.0, y: 6.0 }, Point { x: 4.0, y: 5.0 }]);
+    assert_eq!(
+        PolygonWithHoles::try_new(big, vec![square(4.0, 4.0, 2.0), diamond]),
+        Err(PolyError::OverlappingHoles { first: 0, second: 1 })
+    );
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_polygon_with_holes_area_and_containment() {
+    let polygon = square_with_hole();
+    assert_eq!(polygon.area(), 12.0);
+    assert!(polygon.is_point_inside(Point { x: 0.5, y: 0.5 }));
+    assert!(!polygon.is_point_inside(Point { x: 2.0, y: 2.0 }));
+    assert!(polygon.is_point_inside(Point { x: 1.0, y: 2.0 })); // on the edge of the hole
+    assert!(polygon.is_point_inside(Point { x: 4.0, y: 2.0 }));
+    assert!(!polygon.is_point_inside(Point { x: 5.0, y: 2.0 }));
+    assert_eq!(polygon.bounding_box(), Some((Point { x: 0.0, y: 0.0 }, Point { x: 4.0, y: 4.0 })));
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_multipolygon_area_containment_and_bounds() {
+    let multi = MultiPolygon { polygons: vec![square_with_hole(), square(5.0, -1.0, 1.0).into()] };
+    assert_eq!(multi.area(), 13.0);
+    assert!(multi.is_point_inside(Point { x: 5.5, y: -0.5 }));
+    assert!(multi.is_point_inside(Point { x: 0.5, y: 3.5 }));
+    assert!(!multi.is_point_inside(Point { x: 2.0, y: 2.0 }));
+    assert!(!multi.is_point_inside(Point { x: 4.5, y: 0.0 }));
+    assert_eq!(multi.bounding_box(), Some((Point { x: 0.0, y: -1.0 }, Point { x: 6.0, y: 4.0 })));
+
+    let empty = MultiPolygon { polygons: vec![] };
+    assert!(empty.is_empty());
+    assert_eq!(empty.area(), 0.0);
+    assert_eq!(empty.bounding_box(), None);
+
+    let wide: generic::MultiPolygon<f64> = multi.clone().into();
+    assert_eq!(wide.area(), 13.0);
+    assert_eq!(wide.cast::<f32>(), multi);
+}
+
+#[test]
+fn test_generate_svg_from_multipolygons() {
+    let multi: MultiPolygon = square_with_hole().into();
+    let triangle: MultiPolygon = PolygonWithHoles::from(Polygon::new(&[
+        Point { x: 5.0, y: 0.0 },
+        Point { x: 6.0, y: 0.0 },
+        Point { x: 5.0, y: 1.0 },
+    ]))
+    .into();
+    let svg = generate_svg_from_multipolygons(&[&multi, &triangle], &["red", "blue"], 100, 100, None);
+    assert_eq!(
+        svg,
+        "<svg width=\"100\" height=\"100\" xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 6 4\">\
+         <path d=\"M0,0 L4,0 L4,4 L0,4 Z M1,3 L3,3 L3,1 L1,1 Z\" fill=\"red\" fill-opacity=\"0.3\" fill-rule=\"evenodd\" stroke=\"red\" stroke-width=\"0.1\" />\
+         <path d=\"M5,0 L6,0 L5,1 Z\" fill=\"blue\" fill-opacity=\"0.3\" fill-rule=\"evenodd\" stroke=\"blue\" stroke-width=\"0.1\" />\
+         </svg>"
+    );
+}
+
+#[test]
+fn test_hull_of_scattered_points() {
+    let points = vec![
+        Point { x: 1.0, y: 1.0 },
+        Point { x: 2.0, y: 2.0 },
+        Point { x: 0.0, y: 2.0 },
+        Point { x: 1.0, y: 0.5 },
+        Point { x: 2.0, y: 0.0 },
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 1.0, y: 1.5 },
+        Point { x: 0.0, y: 0.0 },
+    ];
+    let hull = ConvexPolygon::hull(&points);
+    assert_eq!(
+        hull.vertices,
+        vec![Point { x: 0.0, y: 0.0 }, Point { x: 2.0, y: 0.0 }, Point { x: 2.0, y: 2.0 }, Point { x: 0.0, y: 2.0 }]
+    );
+    assert!(check_polygon_is_convex(&hull.vertices));
+}
+
+#[test]
+fn test_hull_keeps_collinear_points_on_request() {
+    let points = vec![
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 1.0, y: 0.0 },
+        Point { x: 2.0, y: 0.0 },
+        Point { x: 2.0, y: 1.0 },
+        Point { x: 2.0, y: 2.0 },
+        Point { x: 0.0, y: 2.0 },
+        Point { x: 0.0, y: 1.0 },
+        Point { x: 1.0, y: 1.0 },
+    ];
+    assert_eq!(ConvexPolygon::hull(&points).vertices.len(), 4);
+    let hull = ConvexPolygon::try_hull_with_collinear(&points, true).unwrap();
+    assert_eq!(
+        hull.vertices,
+        vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 1.0, y: 0.0 },
+            Point { x: 2.0, y: 0.0 },
+            Point { x: 2.0, y: 1.0 },
+            Point { x: 2.0, y: 2.0 },
+            Point { x: 0.0, y: 2.0 },
+            Point { x: 0.0, y: 1.0 },
+        ]
+    );
+    assert!(check_polygon_is_convex(&hull.vertices));
+}
+
+#[test]
+fn test_hull_errors() {
+    let p = |x, y| Point { x, y };
+    assert_eq!(ConvexPolygon::try_hull(&[p(0.0, 0.0), p(1.0, 1.0)]), Err(PolyError::TooFewVertices { count: 2 }));
+    assert_eq!(ConvexPolygon::try_hull(&[p(0.0, 0.0), p(1.0, 1.0), p(f32::NAN, 0.0)]), Err(PolyError::NonFinite { index: 2 }));
+    let collinear = [p(0.0, 0.0), p(2.0, 2.0), p(1.0, 1.0), p(3.0, 3.0)];
+    assert_eq!(ConvexPolygon::try_hull(&collinear), Err(PolyError::Degenerate));
+    assert_eq!(ConvexPolygon::try_hull_with_collinear(&collinear, true), Err(PolyError::Degenerate));
+    assert_eq!(ConvexPolygon::try_hull(&[p(1.0, 1.0); 5]), Err(PolyError::Degenerate));
+}
+
+#[test]
+fn test_hull_builder_matches_batch_hull() {
+    let mut rng = Lcg(99);
+    for round in 0..200 {
+        let grid = round % 2 == 0;
+        let points: Vec<generic::Point<f64>> = (0..3 + rng.next_below(40))
+            .map(|_| {
+                let (x, y) = (rng.next_f64() * 10.0, rng.next_f64() * 10.0);
+                if grid { generic::Point::new(x.round(), y.round()) } else { generic::Point::new(x, y) }
+            })
+            .collect();
+        let mut builder = generic::HullBuilder::new();
+        builder.extend(points.iter().copied()).unwrap();
+        assert_eq!(builder.len(), points.len());
+        let batch = generic::ConvexPolygon::try_hull(&points);
+        assert_eq!(builder.hull(), batch, "{points:?}");
+        if let Ok(hull) = batch {
+            assert!(check_polygon_is_convex(&hull.vertices));
+            assert!(points.iter().all(|p| hull.is_point_inside(*p)));
+        }
+    }
+}
+
+#[test]
+fn test_hull_builder_streaming() {
+    let mut builder = HullBuilder::new();
+    assert!(builder.is_empty());
+    builder.add(Point { x: 0.0, y: 0.0 }).unwrap();
+    builder.add(Point { x: 1.0, y: 0.0 }).unwrap();
+    assert_eq!(builder.hull(), Err(PolyError::TooFewVertices { count: 2 }));
+    builder.add(Point { x: 3.0, y: 0.0 }).unwrap();
+    assert_eq!(builder.hull(), Err(PolyError::Degenerate));
+    assert_eq!(builder.add(Point { x: f32::INFINITY, y: 0.0 }), Err(PolyError::NonFinite { index: 3 }));
+    builder.add(Point { x: 1.0, y: 2.0 }).unwrap();
+    assert_eq!(
+        builder.hull().unwrap().vertices,
+        vec![Point { x: 0.0, y: 0.0 }, Point { x: 3.0, y: 0.0 }, Point { x: 1.0, y: 2.0 }]
+    );
+    builder.add(Point { x: 1.0, y: 1.0 }).unwrap();
+    builder.add(Point { x: -1.0, y: -1.0 }).unwrap();
+    assert_eq!(
+        builder.hull().unwrap().vertices,
+        vec![Point { x: -1.0, y: -1.0 }, Point { x: 3.0, y: 0.0 }, Point { x: 1.0, y: 2.0 }]
+    );
+    assert_eq!(builder.len(), 6);
+}
+
+#[test]
+fn test_convex_polygon_area_perimeter_and_orientation() {
+    let rectangle = ConvexPolygon::new(&[
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 4.0, y: 0.0 },
+        Point { x: 4.0, y: 2.0 },
+        Point { x: 0.0, y: 2.0 },
+    ]);
+    assert_eq!(rectangle.signed_area(), 8.0);
+    assert_eq!(rectangle.area(), 8.0);
+    assert_eq!(rectangle.perimeter(), 12.0);
+    assert_eq!(rectangle.orientation(), Orientation::CounterClockwise);
+
+    let clockwise = ConvexPolygon { vertices: rectangle.vertices.iter().rev().copied().collect() };
+    assert_eq!(clockwise.signed_area(), -8.0);
+    assert_eq!(clockwise.area(), 8.0);
+    assert_eq!(clockwise.orientation(), Orientation::Clockwise);
+}
+
+#[test]
+fn test_convex_polygon_centroid_and_second_moments() {
+    // a right triangle, whose centroid is not the vertex average once a vertex is repeated along an edge
+    let triangle = ConvexPolygon::try_hull_with_collinear(
+        &[Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 0.0 }, Point { x: 3.0, y: 0.0 }, Point { x: 0.0, y: 3.0 }],
+        true,
+    )
+    .unwrap();
+    assert_eq!(triangle.vertices.len(), 4);
+    assert_eq!(triangle.centroid(), Point { x: 1.0, y: 1.0 });
+
+    // b * h³ / 12 for a 4 x 2 rectangle, about its own centre
+    let rectangle = generic::ConvexPolygon::<f64>::new(&[
+        generic::Point::new(10.0, 20.0),
+        generic::Point::new(14.0, 20.0),
+        generic::Point::new(14.0, 22.0),
+        generic::Point::new(10.0, 22.0),
+    ]);
+    assert_eq!(rectangle.centroid(), generic::Point::new(12.0, 21.0));
+    let moments = rectangle.second_moments();
+    assert!((moments.ixx - 4.0 * 8.0 / 12.0).abs() < 1e-12);
+    assert!((moments.iyy - 64.0 * 2.0 / 12.0).abs() < 1e-12);
+    assert!(moments.ixy.abs() < 1e-12);
+    assert!((moments.polar() - (moments.ixx + moments.iyy)).abs() < 1e-12);
+
+    // the same moments whichever way the vertices run
+    let clockwise = generic::ConvexPolygon { vertices: rectangle.vertices.iter().rev().copied().collect() };
+    assert_eq!(clockwise.centroid(), rectangle.centroid());
+    let reversed = clockwise.second_moments();
+    assert!((reversed.ixx - moments.ixx).abs() < 1e-12 && (reversed.iyy - moments.iyy).abs() < 1e-12);
+
+    // b * h³ / 36 and -b²h² / 72 for a right triangle with its legs along the axes
+    let triangle = generic::ConvexPolygon::<f64>::new(&[generic::Point::new(0.0, 0.0), generic::Point::new(3.0, 0.0), generic::Point::new(0.0, 3.0)]);
+    let moments = triangle.second_moments();
+    assert!((moments.ixx - 81.0 / 36.0).abs() < 1e-12);
+    assert!((moments.ixy + 81.0 / 72.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_intersection_area_matches_boolean_area() {
+    let mut rng = Lcg(3);
+    for _ in 0..100 {
+        let one = circle_polygon(rng.next_f64() * 4.0, rng.next_f64() * 4.0, 1.0 + rng.next_f64() * 2.0, 3 + rng.next_below(20), rng.next_f64());
+        let two = circle_polygon(rng.next_f64() * 4.0, rng.next_f64() * 4.0, 1.0 + rng.next_f64() * 2.0, 3 + rng.next_below(20), rng.next_f64());
+        let convex = one.intersection(&two).into_polygon().map_or(0.0, |p| p.area());
+        let general = generic::Polygon::new(&one.vertices).intersection(&generic::Polygon::new(&two.vertices)).area();
+        assert!((convex - general).abs() < 1e-9);
+        assert!(convex <= one.area().min(two.area()) + 1e-9);
+    }
+}
+
+#[test]
+fn test_aabb_operations() {
+    let p = |x, y| Point { x, y };
+    let one = Aabb::new(p(2.0, 2.0), p(0.0, 0.0));
+    assert_eq!(one, Aabb { min: p(0.0, 0.0), max: p(2.0, 2.0) });
+    let two = Aabb::new(p(1.0, 1.0), p(3.0, 4.0));
+    assert_eq!(one.union(&two), Aabb::new(p(0.0, 0.0), p(3.0, 4.0)));
+    assert_eq!(one.intersection(&two), Aabb::new(p(1.0, 1.0), p(2.0, 2.0)));
+    assert!(one.intersects(&two));
+    assert!(one.contains_point(p(2.0, 1.0)));
+    assert!(!one.contains_point(p(2.5, 1.0)));
+    assert!(one.union(&two).contains(&one));
+    assert!(!one.contains(&two));
+    assert_eq!(one.expand(0.5), Aabb::new(p(-0.5, -0.5), p(2.5, 2.5)));
+    assert_eq!(one.include(p(-1.0, 5.0)), Aabb::new(p(-1.0, 0.0), p(2.0, 5.0)));
+    assert_eq!((two.width(), two.height(), two.center()), (2.0, 3.0, p(2.0, 2.5)));
+
+    // boxes that only touch share their edge
+    let beside = Aabb::new(p(2.0, 0.0), p(3.0, 1.0));
+    assert_eq!(one.intersection(&beside), Aabb::new(p(2.0, 0.0), p(2.0, 1.0)));
+    let far = Aabb::new(p(5.0, 5.0), p(6.0, 6.0));
+    assert!(!one.intersects(&far));
+    assert!(one.intersection(&far).is_empty());
+
+    let empty = Aabb::empty();
+    assert!(empty.is_empty());
+    assert_eq!(empty.union(&one), one);
+    assert!(one.contains(&empty));
+    assert!(!empty.intersects(&one));
+    assert_eq!(Aabb::from_points(&[]), empty);
+    assert_eq!(Aabb::from_points(&[p(1.0, 5.0), p(-2.0, 3.0), p(0.0, 0.0)]), Aabb::new(p(-2.0, 0.0), p(1.0, 5.0)));
+}
+
+#[test]
+fn test_bounds_of_shapes() {
+    let triangle = ConvexPolygon::new(&[Point { x: 1.0, y: 1.0 }, Point { x: 3.0, y: 1.0 }, Point { x: 3.0, y: 3.0 }]);
+    assert_eq!(triangle.bounds(), Aabb::new(Point { x: 1.0, y: 1.0 }, Point { x: 3.0, y: 3.0 }));
+    assert_eq!(Polygon::new(&l_shape()).bounds(), Aabb::new(Point { x: 0.0, y: 0.0 }, Point { x: 4.0, y: 4.0 }));
+    let segment = Segment::new(Point { x: 2.0, y: -1.0 }, Point { x: 0.0, y: 1.0 });
+    assert_eq!(segment.bounds(), Aabb::new(Point { x: 0.0, y: -1.0 }, Point { x: 2.0, y: 1.0 }));
+    let wide: generic::Aabb<f64> = triangle.bounds().into();
+    assert_eq!(wide.max, generic::Point::new(3.0, 3.0));
+
+    let with_hole = square_with_hole();
+    assert_eq!(with_hole.bounds(), Aabb::new(Point { x: 0.0, y: 0.0 }, Point { x: 4.0, y: 4.0 }));
+    let multi = MultiPolygon { polygons: vec![with_hole, PolygonWithHoles::new(square(5.0, -1.0, 1.0), vec![])] };
+    assert_eq!(multi.bounds(), Aabb::new(Point { x: 0.0, y: -1.0 }, Point { x: 6.0, y: 4.0 }));
+    assert!(MultiPolygon { polygons: vec![] }.bounds().is_empty());
+}
+
+#[test]
+fn test_disjoint_bounds_skip_intersection() {
+    let square = ConvexPolygon::new(&[
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 1.0, y: 0.0 },
+        Point { x: 1.0, y: 1.0 },
+        Point { x: 0.0, y: 1.0 },
+    ]);
+    // this vertex list is not convex, but the boxes are far enough apart that it is never looked at
+    let far = ConvexPolygon {
+        vertices: vec![
+            Point { x: 5.0, y: 5.0 },
+            Point { x: 9.0, y: 5.0 },
+            Point { x: 6.0, y: 6.0 },
+            Point { x: 9.0, y: 9.0 },
+            Point { x: 5.0, y: 9.0 },
+        ],
+    };
+    assert_eq!(try_intersect_convex_polygons(&square, &far), Ok(vec![]));
+    let segment = Segment::new(Point { x: 7.0, y: 4.0 }, Point { x: 7.0, y: 10.0 });
+    assert!(far.try_intersect_with_segment(&segment).is_err());
+    let segment = Segment::new(Point { x: 4.0, y: 0.5 }, Point { x: 10.0, y: 0.5 });
+    assert_eq!(far.try_intersect_with_segment(&segment), Ok((None, None)));
+
+    // boxes that only touch still get the full test
+    let beside = ConvexPolygon::new(&[
+        Point { x: 1.0, y: 0.0 },
+        Point { x: 2.0, y: 0.0 },
+        Point { x: 2.0, y: 1.0 },
+        Point { x: 1.0, y: 1.0 },
+    ]);
+    assert_eq!(
+        square.intersection(&beside),
+        ConvexIntersection::Segment(Segment::new(Point { x: 1.0, y: 0.0 }, Point { x: 1.0, y: 1.0 }))
+    );
+}
+
+fn unit_square_at(x: f32, y: f32) -> ConvexPolygon {
+    ConvexPolygon::new(&[
+        Point { x, y },
+        Point { x: x + 1.0, y },
+        Point { x: x + 1.0, y: y + 1.0 },
+        Point { x, y: y + 1.0 },
+    ])
+}
+
+#[test]
+fn test_overlaps_and_penetration() {
+    let square = unit_square_at(0.0, 0.0);
+
+    let right = unit_square_at(0.75, 0.25);
+    assert!(square.overlaps(&right));
+    assert_eq!(square.penetration(&right), Some((Point { x: 1.0, y: 0.0 }, 0.25)));
+    assert_eq!(right.penetration(&square), Some((Point { x: -1.0, y: 0.0 }, 0.25)));
+
+    let below = unit_square_at(0.25, -0.875);
+    assert_eq!(square.penetration(&below), Some((Point { x: 0.0, y: -1.0 }, 0.125)));
+
+    let touching = unit_square_at(1.0, 0.5);
+    assert!(square.overlaps(&touching));
+    assert_eq!(square.penetration(&touching), Some((Point { x: 1.0, y: 0.0 }, 0.0)));
+
+    let apart = unit_square_at(1.5, 0.0);
+    assert!(!square.overlaps(&apart));
+    assert_eq!(square.penetration(&apart), None);
+
+    // separated only along a diagonal edge normal
+    let triangle = ConvexPolygon::new(&[Point { x: 2.0, y: 0.5 }, Point { x: 2.0, y: 2.0 }, Point { x: 0.5, y: 2.0 }]);
+    assert!(!square.overlaps(&triangle));
+    assert!(triangle.bounds().intersects(&square.bounds()));
+}
+
+#[test]
+fn test_penetration_separates_polygons() {
+    let mut rng = Lcg(11);
+    for _ in 0..300 {
+        let one = circle_polygon(rng.next_f64() * 4.0, rng.next_f64() * 4.0, 0.5 + rng.next_f64() * 2.0, 3 + rng.next_below(12), rng.next_f64());
+        let two = circle_polygon(rng.next_f64() * 4.0, rng.next_f64() * 4.0, 0.5 + rng.next_f64() * 2.0, 3 + rng.next_below(12), rng.next_f64());
+        assert_eq!(one.overlaps(&two), !one.intersection(&two).is_empty(), "{one:?} {two:?}");
+        if let Some((normal, depth)) = one.penetration(&two) {
+            assert!(((normal.x * normal.x + normal.y * normal.y) - 1.0).abs() < 1e-12);
+            // moving a little further than the depth pulls them apart, a little less does not
+            let moved = |d: f64| generic::ConvexPolygon {
+                vertices: two.vertices.iter().map(|v| generic::Point::new(v.x + normal.x * d, v.y + normal.y * d)).collect(),
+            };
+            assert!(!one.overlaps(&moved(depth + 1e-9)), "{one:?} {two:?}");
+            if depth > 1e-9 {
+                assert!(one.overlaps(&moved(depth - 1e-9)), "{one:?} {two:?}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_overlaps_matches_intersection_for_touching_shapes() {
+    let mut rng = Lcg(5);
+    for _ in 0..1000 {
+        let one = grid_polygon(&mut rng);
+        let two = grid_polygon(&mut rng);
+        assert_eq!(one.overlaps(&two), !one.intersection(&two).is_empty(), "{one:?} {two:?}");
+    }
+}
+
+/// The distance between two disjoint convex polygons, from every vertex-to-edge pair.
+fn brute_force_distance(one: &generic::ConvexPolygon<f64>, two: &generic::ConvexPolygon<f64>) -> f64 {
+    let edges = |p: &generic::ConvexPolygon<f64>| -> Vec<generic::Segment<f64>> {
+        let n = p.vertices.len();
+        (0..n).map(|i| generic::Segment::new(p.vertices[i], p.vertices[(i + 1) % n])).collect()
+    };
+    let mut best = f64::MAX;
+    for (a, b) in [(one, two), (two, one)] {
+        for v in &a.vertices {
+            for e in edges(b) {
+                best = best.min(e.distance_to_point(*v));
+            }
+        }
+    }
+    best
+}
+
+/// Whether `p` is inside `polygon` or within `eps` of its boundary, allowing for rounding
+/// in points computed on an edge.
+fn inside_or_near(polygon: &generic::ConvexPolygon<f64>, p: generic::Point<f64>, eps: f64) -> bool {
+    let n = polygon.vertices.len();
+    polygon.is_point_inside(p) || (0..n).any(|i| generic::Segment::new(polygon.vertices[i], polygon.vertices[(i + 1) % n]).distance_to_point(p) <= eps)
+}
+
+#[test]
+fn test_gjk_distance_to_point_and_segment() {
+    let square = unit_square_at(0.0, 0.0);
+    assert_eq!(square.support(Point { x: 1.0, y: 1.0 }), Point { x: 1.0, y: 1.0 });
+
+    let point = Point { x: 3.0, y: 0.5 };
+    assert_eq!(gjk_distance(&square, &point), 2.0);
+    assert_eq!(gjk_closest_points(&square, &point), Some((Point { x: 1.0, y: 0.5 }, point)));
+    assert_eq!(gjk_distance(&square, &Point { x: 0.5, y: 0.5 }), 0.0);
+    assert_eq!(gjk_closest_points(&square, &Point { x: 0.5, y: 0.5 }), None);
+
+    let corner = Point { x: 4.0, y: 5.0 };
+    assert_eq!(gjk_distance(&square, &corner), 5.0);
+
+    let segment = Segment::new(Point { x: -1.0, y: 3.0 }, Point { x: 3.0, y: 3.0 });
+    assert_eq!(gjk_distance(&square, &segment), 2.0);
+    let (on_square, on_segment) = gjk_closest_points(&square, &segment).unwrap();
+    assert_eq!(on_square.y, 1.0);
+    assert_eq!(on_segment.y, 3.0);
+    assert!((on_square.x - on_segment.x).abs() < 1e-6);
+
+    let crossing = Segment::new(Point { x: -1.0, y: 0.5 }, Point { x: 3.0, y: 0.5 });
+    assert_eq!(gjk_distance(&square, &crossing), 0.0);
+    let (normal, depth) = epa_penetration(&square, &crossing).unwrap();
+    assert_eq!(depth, 0.5);
+    assert_eq!(normal.x, 0.0);
+}
+
+#[test]
+fn test_gjk_matches_brute_force_distance() {
+    let mut rng = Lcg(21);
+    let mut separated = 0;
+    for _ in 0..300 {
+        let one = circle_polygon(rng.next_f64() * 8.0, rng.next_f64() * 8.0, 0.5 + rng.next_f64() * 2.0, 3 + rng.next_below(12), rng.next_f64());
+        let two = circle_polygon(rng.next_f64() * 8.0, rng.next_f64() * 8.0, 0.5 + rng.next_f64() * 2.0, 3 + rng.next_below(12), rng.next_f64());
+        match gjk_closest_points(&one, &two) {
+            Some((a, b)) => {
+                separated += 1;
+                assert!(!one.overlaps(&two), "{one:?} {two:?}");
+                let expected = brute_force_distance(&one, &two);
+                assert!((a.distance(b) - expected).abs() < 1e-9, "{one:?} {two:?}");
+                assert!(inside_or_near(&one, a, 1e-9) && inside_or_near(&two, b, 1e-9), "{one:?} {two:?}");
+                assert_eq!(gjk_distance(&one, &two), a.distance(b));
+                assert_eq!(epa_penetration(&one, &two), None);
+            }
+            None => assert!(one.overlaps(&two), "{one:?} {two:?}"),
+        }
+    }
+    assert!(separated > 50);
+}
+
+#[test]
+fn test_epa_matches_sat_penetration() {
+    let mut rng = Lcg(8);
+    let mut overlapping = 0;
+    for _ in 0..300 {
+        let one = circle_polygon(rng.next_f64() * 3.0, rng.next_f64() * 3.0, 0.5 + rng.next_f64() * 2.0, 3 + rng.next_below(12), rng.next_f64());
+        let two = circle_polygon(rng.next_f64() * 3.0, rng.next_f64() * 3.0, 0.5 + rng.next_f64() * 2.0, 3 + rng.next_below(12), rng.next_f64());
+        if let Some((sat_normal, sat_depth)) = one.penetration(&two) {
+            overlapping += 1;
+            let (normal, depth) = epa_penetration(&one, &two).unwrap();
+            assert!((depth - sat_depth).abs() < 1e-9, "{one:?} {two:?}: {depth} {sat_depth}");
+            if sat_depth > 1e-6 {
+                assert!(sat_normal.distance(normal) < 1e-6 || (depth - sat_depth).abs() < 1e-12, "{one:?} {two:?}");
+            }
+        }
+    }
+    assert!(overlapping > 50);
+}
+
+#[test]
+fn test_collinear_segment_intersection() {
+    let seg_one = Segment::new(Point { x: 0.0, y: 0.0 }, Point { x: 4.0, y: 0.0 });
+
+    let inside = Segment::new(Point { x: 3.0, y: 0.0 }, Point { x: 1.0, y: 0.0 });
+    assert_eq!(seg_one.intersection(&inside), SegmentIntersection::Overlap(Segment::new(Point { x: 1.0, y: 0.0 }, Point { x: 3.0, y: 0.0 })));
+    assert_eq!(inside.intersection(&seg_one), SegmentIntersection::Overlap(inside));
+    assert_eq!(intersect_line_segments(&seg_one, &inside), None);
+
+    let past_end = Segment::new(Point { x: 2.0, y: 0.0 }, Point { x: 6.0, y: 0.0 });
+    assert_eq!(seg_one.intersection(&past_end), SegmentIntersection::Overlap(Segment::new(Point { x: 2.0, y: 0.0 }, Point { x: 4.0, y: 0.0 })));
+
+    let end_to_end = Segment::new(Point { x: 4.0, y: 0.0 }, Point { x: 6.0, y: 0.0 });
+    assert_eq!(seg_one.intersection(&end_to_end), SegmentIntersection::Point(Point { x: 4.0, y: 0.0 }));
+    assert_eq!(intersect_line_segments(&seg_one, &end_to_end), Some(Point { x: 4.0, y: 0.0 }));
+
+    let apart = Segment::new(Point { x: 5.0, y: 0.0 }, Point { x: 6.0, y: 0.0 });
+    assert!(seg_one.intersection(&apart).is_none());
+    let parallel = Segment::new(Point { x: 0.0, y: 1.0 }, Point { x: 4.0, y: 1.0 });
+    assert!(seg_one.intersection(&parallel).is_none());
+
+    let crossing = Segment::new(Point { x: 1.0, y: -1.0 }, Point { x: 1.0, y: 1.0 });
+    assert_eq!(seg_one.intersection(&crossing), SegmentIntersection::Point(Point { x: 1.0, y: 0.0 }));
+}
+
+#[test]
+fn test_segment_along_polygon_edge() {
+    let square = unit_square_at(0.0, 0.0);
+    let along = Segment::new(Point { x: 0.25, y: 0.0 }, Point { x: 0.75, y: 0.0 });
+    assert_eq!(square.intersect_with_segment(&along), (Some(along.p1), Some(along.p2)));
+
+    let polygon = Polygon::new(&l_shape());
+    let along = Segment::new(Point { x: 0.5, y: 0.0 }, Point { x: 1.5, y: 0.0 });
+    assert_eq!(polygon.intersect_with_segment(&along), vec![along.p1, along.p2]);
+
+    // polygons sharing part of an edge keep the ends of the shared part
+    let big = ConvexPolygon::new(&[Point { x: 0.0, y: 0.0 }, Point { x: 2.0, y: 0.0 }, Point { x: 2.0, y: 2.0 }, Point { x: 0.0, y: 2.0 }]);
+    let beside = ConvexPolygon::new(&[Point { x: 2.0, y: 0.5 }, Point { x: 4.0, y: 0.5 }, Point { x: 4.0, y: 1.5 }, Point { x: 2.0, y: 1.5 }]);
+    let points = intersect_convex_polygons(&big, &beside);
+    assert!(points.contains(&Point { x: 2.0, y: 0.5 }) && points.contains(&Point { x: 2.0, y: 1.5 }));
+    assert!(points.iter().all(|p| p.x == 2.0));
+}
+
+#[test]
+fn test_classify_point_with_tolerance() {
+    let square = generic::ConvexPolygon::new(&[
+        generic::Point::new(100000.0, 100000.0),
+        generic::Point::new(100001.0, 100000.0),
+        generic::Point::new(100001.0, 100001.0),
+        generic::Point::new(100000.0, 100001.0),
+    ]);
+    // only vertices snap; near the middle of an edge the side is decided exactly
+    let near_edge = generic::Point::new(100001.0 + 1e-10, 100000.5);
+    assert_eq!(square.classify_point(near_edge), PointLocation::Outside);
+    assert_eq!(square.classify_point_binary_search(near_edge), PointLocation::Outside);
+    let on_edge = generic::Point::new(100001.0, 100000.5);
+    assert_eq!(square.classify_point(on_edge), PointLocation::OnEdge(1));
+    assert_eq!(square.classify_point_binary_search(on_edge), PointLocation::OnEdge(1));
+
+    let near_corner = generic::Point::new(100000.0 - 1e-11, 100001.0 + 1e-11);
+    assert_eq!(square.classify_point(near_corner), PointLocation::OnVertex(3));
+    assert_eq!(square.classify_point_binary_search(near_corner), PointLocation::OnVertex(3));
+    assert_eq!(square.classify_point_with_tolerance(near_corner, &generic::Tolerance::exact()), PointLocation::Outside);
+    assert_eq!(square.classify_point_binary_search_with_tolerance(near_corner, &generic::Tolerance::exact()), PointLocation::Outside);
+    assert!(PointLocation::OnVertex(3).is_on_boundary() && !PointLocation::Inside.is_on_boundary());
+
+    let polygon = Polygon::new(&l_shape());
+    assert_eq!(polygon.classify_point(Point { x: 3.0, y: 3.0 }), PointLocation::Outside);
+    assert_eq!(polygon.classify_point(Point { x: 3.0, y: 2.0 }), PointLocation::OnEdge(2));
+    assert_eq!(polygon.classify_point(Point { x: 2.0, y: 2.0 }), PointLocation::OnVertex(3));
+    assert_eq!(polygon.classify_point(Point { x: 1.0, y: 3.0 }), PointLocation::Inside);
+}
+
+#[test]
+fn test_classify_point_binary_search_matches_linear() {
+    let mut rng = Lcg(16);
+    for _ in 0..100 {
+        let polygon = circle_polygon(rng.next_f64() * 4.0, rng.next_f64() * 4.0, 0.5 + rng.next_f64() * 2.0, 3 + rng.next_below(40), rng.next_f64());
+        let n = polygon.vertices.len();
+        for _ in 0..50 {
+            let p = generic::Point::new(rng.next_f64() * 8.0 - 2.0, rng.next_f64() * 8.0 - 2.0);
+            assert_eq!(polygon.classify_point_binary_search(p), polygon.classify_point(p), "{polygon:?} {p:?}");
+        }
+        for i in 0..n {
+            let (a, b) = (polygon.vertices[i], polygon.vertices[(i + 1) % n]);
+            let exact = generic::Tolerance::exact();
+            assert_eq!(polygon.classify_point_binary_search_with_tolerance(a, &exact), PointLocation::OnVertex(i));
+            let mid = generic::Point::new((a.x + b.x) / 2.0, (a.y + b.y) / 2.0);
+            assert_eq!(polygon.classify_point_binary_search(mid), polygon.classify_point(mid), "{polygon:?} {mid:?}");
+            // the rounded midpoint may fall either side of the edge, but no further
+            let expected = match orientation(a, b, mid) {
+                Orientation::Collinear => PointLocation::OnEdge(i),
+                Orientation::CounterClockwise => PointLocation::Inside,
+                Orientation::Clockwise => PointLocation::Outside,
+            };
+            assert_eq!(polygon.classify_point(mid), expected, "{polygon:?} {mid:?}");
+        }
+    }
+}
+
+#[test]
+fn test_clip_segment() {
+    let square = unit_square_at(0.0, 0.0);
+    let through = Segment::new(Point { x: -1.0, y: 0.5 }, Point { x: 3.0, y: 0.5 });
+    assert_eq!(square.clip_segment(&through), Some(Segment::new(Point { x: 0.0, y: 0.5 }, Point { x: 1.0, y: 0.5 })));
+    let clip = square.clip_segment_parametric(&through).unwrap();
+    assert_eq!((clip.t0, clip.t1), (0.25, 0.5));
+    // vertices run (0,0), (1,0), (1,1), (0,1), so edge 3 is x = 0 and edge 1 is x = 1
+    assert_eq!((clip.entry_edge, clip.exit_edge), (Some(3), Some(1)));
+
+    let starts_inside = Segment::new(Point { x: 0.5, y: 0.5 }, Point { x: 0.5, y: 2.0 });
+    let clip = square.clip_segment_parametric(&starts_inside).unwrap();
+    assert_eq!(clip.segment(), Segment::new(Point { x: 0.5, y: 0.5 }, Point { x: 0.5, y: 1.0 }));
+    assert_eq!((clip.entry_edge, clip.exit_edge), (None, Some(2)));
+
+    let inside = Segment::new(Point { x: 0.25, y: 0.25 }, Point { x: 0.75, y: 0.5 });
+    assert_eq!(square.clip_segment(&inside), Some(inside));
+    let missing = Segment::new(Point { x: -1.0, y: 2.0 }, Point { x: 3.0, y: 2.0 });
+    assert_eq!(square.clip_segment(&missing), None);
+    let short = Segment::new(Point { x: -1.0, y: 0.5 }, Point { x: -0.5, y: 0.5 });
+    assert_eq!(square.clip_segment(&short), None);
+
+    // running along an edge, and touching a corner
+    let along = Segment::new(Point { x: -1.0, y: 0.0 }, Point { x: 2.0, y: 0.0 });
+    assert_eq!(square.clip_segment(&along), Some(Segment::new(Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 0.0 })));
+    let corner = Segment::new(Point { x: 0.0, y: 2.0 }, Point { x: 2.0, y: 0.0 });
+    assert_eq!(square.clip_segment(&corner), Some(Segment::new(Point { x: 1.0, y: 1.0 }, Point { x: 1.0, y: 1.0 })));
+}
+
+#[test]
+fn test_clip_ray_and_line() {
+    let square = unit_square_at(0.0, 0.0);
+    let clip = square.clip_ray(Point { x: -1.0, y: 0.5 }, Point { x: 2.0, y: 0.0 }).unwrap();
+    assert_eq!((clip.t0, clip.t1), (0.5, 1.0));
+    assert_eq!((clip.start, clip.end), (Point { x: 0.0, y: 0.5 }, Point { x: 1.0, y: 0.5 }));
+    assert_eq!(square.clip_ray(Point { x: -1.0, y: 0.5 }, Point { x: -1.0, y: 0.0 }), None);
+    let from_inside = square.clip_ray(Point { x: 0.5, y: 0.5 }, Point { x: 0.0, y: -1.0 }).unwrap();
+    assert_eq!((from_inside.t0, from_inside.t1, from_inside.entry_edge, from_inside.exit_edge), (0.0, 0.5, None, Some(0)));
+
+    // the diagonal x = y, with direction (-b, a) = (1, 1)
+    let line = Line::from_points(Point { x: 5.0, y: 5.0 }, Point { x: 6.0, y: 6.0 });
+    let clip: Clip = square.clip_line(&line).unwrap();
+    assert_eq!((clip.start, clip.end), (Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 1.0 }));
+    assert_eq!(clip.t1 - clip.t0, 1.0);
+    assert!(clip.entry_edge.is_some() && clip.exit_edge.is_some());
+    assert_eq!(square.clip_line(&Line::from_points(Point { x: 0.0, y: 2.0 }, Point { x: 1.0, y: 2.0 })), None);
+}
+
+#[test]
+fn test_clip_segment_matches_boundary_crossings() {
+    let mut rng = Lcg(17);
+    for _ in 0..200 {
+        let polygon = circle_polygon(0.0, 0.0, 1.0 + rng.next_f64() * 2.0, 3 + rng.next_below(20), rng.next_f64());
+        let segment = generic::Segment::new(
+            generic::Point::new(rng.next_f64() * 8.0 - 4.0, rng.next_f64() * 8.0 - 4.0),
+            generic::Point::new(rng.next_f64() * 8.0 - 4.0, rng.next_f64() * 8.0 - 4.0),
+        );
+        match polygon.clip_segment_parametric(&segment) {
+            Some(clip) => {
+                assert!(clip.t0 <= clip.t1 && clip.t0 >= 0.0 && clip.t1 <= 1.0);
+                let mid = generic::Point::new((clip.start.x + clip.end.x) / 2.0, (clip.start.y + clip.end.y) / 2.0);
+                assert!(polygon.is_point_inside(mid), "{polygon:?} {segment:?}");
+                for (t, edge) in [(clip.t0, clip.entry_edge), (clip.t1, clip.exit_edge)] {
+                    let p = generic::Point::new(segment.p1.x + t * (segment.p2.x - segment.p1.x), segment.p1.y + t * (segment.p2.y - segment.p1.y));
+                    match edge {
+                        Some(i) => {
+                            let n = polygon.vertices.len();
+                            let e = generic::Segment::new(polygon.vertices[i], polygon.vertices[(i + 1) % n]);
+                            assert!(e.distance_to_point(p) < 1e-9, "{polygon:?} {segment:?}");
+                        }
+                        None => assert!(polygon.is_point_inside(p)),
+                    }
+                }
+            }
+            None => {
+                assert!(!polygon.is_point_inside(segment.p1) && !polygon.is_point_inside(segment.p2));
+                assert!(polygon.try_intersect_with_segment(&segment).unwrap().0.is_none(), "{polygon:?} {segment:?}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_clip_ring_to_convex_window() {
+    let window = ConvexPolygon::new(&[
+        Point { x: 1.0, y: 1.0 },
+        Point { x: 3.0, y: 1.0 },
+        Point { x: 3.0, y: 3.0 },
+        Point { x: 1.0, y: 3.0 },
+    ]);
+    // the L shape's notch is the top right quarter of the window
+    let clipped = window.clip_ring(&l_shape());
+    assert_eq!(clipped, vec![
+        Point { x: 1.0, y: 3.0 },
+        Point { x: 1.0, y: 1.0 },
+        Point { x: 3.0, y: 1.0 },
+        Point { x: 3.0, y: 2.0 },
+        Point { x: 2.0, y: 2.0 },
+        Point { x: 2.0, y: 3.0 },
+    ]);
+
+    // a clockwise subject stays clockwise, and a subject outside the window vanishes
+    let mut reversed = l_shape();
+    reversed.reverse();
+    let clipped = window.clip_ring(&reversed);
+    assert!(Polygon::new(&clipped).area() == 3.0);
+    assert!(ConvexPolygon { vertices: clipped }.signed_area() < 0.0);
+    assert!(window.clip_ring(&square(5.0, 5.0, 1.0).vertices).is_empty());
+    assert_eq!(unit_square_at(1.5, 1.5).clip_ring(&window.vertices).len(), 4);
+}
+
+#[test]
+fn test_clip_ring_area_matches_intersection() {
+    let mut rng = Lcg(18);
+    for _ in 0..300 {
+        let Some(subject) = star_polygon(&mut rng, false) else { continue };
+        let window = circle_polygon(rng.next_f64() * 4.0, rng.next_f64() * 4.0, 0.5 + rng.next_f64() * 2.0, 3 + rng.next_below(8), rng.next_f64());
+        let clipped = window.clip_ring(&subject.vertices);
+        // Zero-width bridges between pieces add no area.
+        let clipped_area = generic::ConvexPolygon { vertices: clipped.clone() }.area();
+        let expected = subject.intersection(&generic::Polygon::new(&window.vertices)).area();
+        assert!((clipped_area - expected).abs() < 1e-9, "{subject:?} {window:?}: {clipped_area} {expected}");
+        assert!(clipped.iter().all(|p| inside_or_near(&window, *p, 1e-9)));
+    }
+}
+
+#[test]
+fn test_ray_cast() {
+    let square = unit_square_at(2.0, 0.0);
+    let ray = Ray::new(Point { x: 0.0, y: 0.5 }, Point { x: 2.0, y: 0.0 });
+    assert_eq!(ray.cast(&square), Some(Hit { t: 1.0, point: Point { x: 2.0, y: 0.5 }, normal: Point { x: -1.0, y: 0.0 }, edge: 3 }));
+    assert_eq!(Ray::new(ray.origin, Point { x: -1.0, y: 0.0 }).cast(&square), None);
+    assert_eq!(Ray::new(ray.origin, Point { x: 0.0, y: 0.0 }).cast(&square), None);
+
+    // from inside, the ray hits the edge it leaves through
+    let inside = Ray::new(Point { x: 2.5, y: 0.5 }, Point { x: 0.0, y: 1.0 });
+    assert_eq!(inside.cast(&square), Some(Hit { t: 0.5, point: Point { x: 2.5, y: 1.0 }, normal: Point { x: 0.0, y: 1.0 }, edge: 2 }));
+
+    // grazing the top right corner
+    let graze = Ray::new(Point { x: 2.0, y: 2.0 }, Point { x: 1.0, y: -1.0 });
+    let hit = graze.cast(&square).unwrap();
+    assert_eq!((hit.t, hit.point), (1.0, Point { x: 3.0, y: 1.0 }));
+
+    let precise: generic::Ray<f64> = ray.into();
+    assert_eq!(precise.at(0.5), generic::Point::new(1.0, 0.5));
+    let wide: generic::Hit<f64> = hit.into();
+    assert_eq!((wide.t, wide.point, wide.edge), (1.0, generic::Point::new(3.0, 1.0), hit.edge));
+    assert_eq!(precise.cast_scalar::<f32>(), ray);
+    assert_eq!(wide.cast::<f32>(), hit);
+}
+
+#[test]
+fn test_ray_cast_nearest() {
+    let polygons = vec![unit_square_at(6.0, 0.0), unit_square_at(2.0, 0.0), unit_square_at(2.0, 3.0), unit_square_at(4.0, 0.0)];
+    let ray = Ray::new(Point { x: 0.0, y: 0.5 }, Point { x: 1.0, y: 0.0 });
+    let (index, hit) = ray.cast_nearest(&polygons).unwrap();
+    assert_eq!((index, hit.t), (1, 2.0));
+    assert_eq!(Ray::new(Point { x: 0.0, y: 2.0 }, Point { x: 1.0, y: 0.0 }).cast_nearest(&polygons), None);
+    assert_eq!(ray.cast_nearest(&[]), None);
+
+    // against a brute force over every edge
+    let mut rng = Lcg(19);
+    for _ in 0..200 {
+        let polygons: Vec<_> = (0..5)
+            .map(|_| circle_polygon(rng.next_f64() * 10.0, rng.next_f64() * 10.0, 0.5 + rng.next_f64(), 3 + rng.next_below(8), rng.next_f64()))
+            .collect();
+        let angle = rng.next_f64() * 2.0 * std::f64::consts::PI;
+        let ray = generic::Ray::new(generic::Point::new(rng.next_f64() * 10.0, rng.next_f64() * 10.0), generic::Point::new(angle.cos(), angle.sin()));
+        let far = generic::Segment::new(ray.origin, ray.at(100.0));
+        let expected = polygons
+            .iter()
+            .flat_map(|polygon| {
+                let (first, second) = polygon.try_intersect_with_segment(&far).unwrap();
+                [first, second].into_iter().flatten()
+            })
+            .map(|p| ray.origin.distance(p))
+            .fold(f64::MAX, f64::min);
+        match ray.cast_nearest(&polygons) {
+            Some((index, hit)) => {
+                let inside = polygons[index].is_point_inside(ray.origin);
+                assert!((hit.t - expected).abs() < 1e-9, "{polygons:?} {ray:?}");
+                assert!((hit.normal.x.hypot(hit.normal.y) - 1.0).abs() < 1e-12);
+                if !inside {
+                    assert!(hit.normal.x * ray.direction.x + hit.normal.y * ray.direction.y <= 0.0);
+                }
+            }
+            None => assert_eq!(expected, f64::MAX, "{polygons:?} {ray:?}"),
+        }
+    }
+}
+
+#[test]
+fn test_line_api() {
+    // y = x + 1, running up and to the right
+    let line = Line::from_points(Point { x: 0.0, y: 1.0 }, Point { x: 1.0, y: 2.0 });
+    assert_eq!(line.direction(), Point { x: 1.0, y: 1.0 });
+    let unit = line.normalize();
+    assert!((unit.a * unit.a + unit.b * unit.b - 1.0).abs() < 1e-6);
+    assert_eq!(Line::new(0.0, 0.0, 1.0).normalize(), Line::new(0.0, 0.0, 1.0));
+
+    let below = Point { x: 1.0, y: 0.0 };
+    let above = Point { x: 0.0, y: 3.0 };
+    assert!((line.signed_distance_to_point(below) - 2.0_f32.sqrt()).abs() < 1e-6);
+    assert!((line.signed_distance_to_point(above) + 2.0_f32.sqrt()).abs() < 1e-6);
+    assert!((line.distance_to_point(above) - 2.0_f32.sqrt()).abs() < 1e-6);
+    assert_eq!(line.side(below), Orientation::Clockwise);
+    assert_eq!(line.side(above), Orientation::CounterClockwise);
+    assert_eq!(line.side(Point { x: 3.0, y: 4.0 }), Orientation::Collinear);
+    let foot = line.closest_point(below);
+    assert!((foot.x - 0.0).abs() < 1e-6 && (foot.y - 1.0).abs() < 1e-6);
+
+    let horizontal = Line::from_points(Point { x: 0.0, y: 3.0 }, Point { x: 5.0, y: 3.0 });
+    assert_eq!(line.intersection(&horizontal), Some(Point { x: 2.0, y: 3.0 }));
+    assert_eq!(line.intersection(&line.parallel_through(below)), None);
+    assert!((line.angle_between(&horizontal) - std::f32::consts::FRAC_PI_4).abs() < 1e-6);
+    assert_eq!(horizontal.angle_between(&horizontal.parallel_through(below)), 0.0);
+
+    let parallel = line.parallel_through(below);
+    assert_eq!(parallel.side(below), Orientation::Collinear);
+    assert_eq!(parallel.direction(), line.direction());
+    let perpendicular = line.perpendicular_through(below);
+    assert_eq!(perpendicular.side(below), Orientation::Collinear);
+    assert_eq!(perpendicular.direction(), Point { x: -1.0, y: 1.0 });
+    assert!((line.angle_between(&perpendicular) - std::f32::consts::FRAC_PI_2).abs() < 1e-6);
+    let crossing = perpendicular.intersection(&line).unwrap();
+    assert!(crossing.distance(foot) < 1e-6);
+}
+
+#[test]
+fn test_line_side_near_the_line_matches_orientation() {
+    // a = 7, b = -3 and c = 1 come out exact, so side must agree with orientation
+    // for points a few ulps either side of (2.5, 5.5), which is on the line
+    let (p1, p2) = (generic::Point::new(1.0, 2.0), generic::Point::new(4.0, 9.0));
+    let line = generic::Line::from_points(p1, p2);
+    assert_eq!(line, generic::Line::new(7.0, -3.0, 1.0));
+    let ulp = 2f64.powi(-50);
+    for i in -8..8 {
+        for j in -8..8 {
+            let p = generic::Point::new(2.5 + i as f64 * ulp, 5.5 + j as f64 * ulp);
+            assert_eq!(line.side(p), orientation(p1, p2, p), "p={p:?}");
+        }
+    }
+}
+
+#[test]
+fn test_affine_transforms() {
+    let p = Point { x: 1.0, y: 2.0 };
+    assert_eq!(p.transform(&Affine::identity()), p);
+    assert_eq!(p.transform(&Affine::translation(3.0, -1.0)), Point { x: 4.0, y: 1.0 });
+    assert_eq!(p.transform(&Affine::scaling(2.0, 3.0)), Point { x: 2.0, y: 6.0 });
+    assert_eq!(p.transform(&Affine::shear(1.0, 0.0)), Point { x: 3.0, y: 2.0 });
+    let turned = p.transform(&Affine::rotation(std::f32::consts::FRAC_PI_2));
+    assert!(turned.distance(Point { x: -2.0, y: 1.0 }) < 1e-6);
+    let about = Point { x: 2.0, y: 2.0 }.transform(&Affine::rotation_about(Point { x: 1.0, y: 1.0 }, std::f32::consts::PI));
+    assert!(about.distance(Point { x: 0.0, y: 0.0 }) < 1e-6);
+
+    // mirror in y = x
+    let mirror = Affine::reflection(&Line::from_points(Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 1.0 }));
+    assert_eq!(p.transform(&mirror), Point { x: 2.0, y: 1.0 });
+    assert_eq!(mirror.determinant(), -1.0);
+
+    // composition applies the first transform first
+    let scale_then_move = Affine::scaling(2.0, 2.0).then(&Affine::translation(1.0, 0.0));
+    assert_eq!(p.transform(&scale_then_move), Point { x: 3.0, y: 4.0 });
+    let inverse = scale_then_move.inverse().unwrap();
+    assert_eq!(p.transform(&scale_then_move).transform(&inverse), p);
+    assert_eq!(Affine::scaling(1.0, 0.0).inverse(), None);
+
+    let segment = Segment::new(p, Point { x: 3.0, y: 2.0 });
+    assert_eq!(segment.transform(&mirror), Segment::new(Point { x: 2.0, y: 1.0 }, Point { x: 2.0, y: 3.0 }));
+    let line = Line::from_points(Point { x: 0.0, y: 1.0 }, Point { x: 1.0, y: 1.0 }).transform(&Affine::translation(0.0, 2.0));
+    assert_eq!(line.side(Point { x: 5.0, y: 3.0 }), Orientation::Collinear);
+    assert!(line.direction().x > 0.0 && line.direction().y == 0.0);
+    let swapped = Line::from_points(Point { x: 0.0, y: 1.0 }, Point { x: 1.0, y: 1.0 }).transform(&mirror);
+    assert_eq!(swapped.side(Point { x: 1.0, y: 5.0 }), Orientation::Collinear);
+}
+
+#[test]
+fn test_affine_keeps_convex_polygons_counter_clockwise() {
+    let mut rng = Lcg(21);
+    let mut mirrored = 0;
+    for _ in 0..100 {
+        let polygon = circle_polygon(rng.next_f64() * 4.0, rng.next_f64() * 4.0, 0.5 + rng.next_f64() * 2.0, 3 + rng.next_below(10), rng.next_f64());
+        let line = generic::Line::from_points(
+            generic::Point::new(rng.next_f64(), rng.next_f64()),
+            generic::Point::new(rng.next_f64() + 2.0, rng.next_f64()),
+        );
+        let affine = generic::Affine::rotation(rng.next_f64() * 6.0)
+            .then(&generic::Affine::scaling(0.5 + rng.next_f64(), if rng.next_below(2) == 0 { -1.0 } else { 1.0 }))
+            .then(&generic::Affine::shear(rng.next_f64(), 0.0))
+            .then(&generic::Affine::reflection(&line))
+            .then(&generic::Affine::translation(rng.next_f64() * 10.0, rng.next_f64() * 10.0));
+        mirrored += usize::from(affine.determinant() < 0.0);
+        let transformed = polygon.transform(&affine);
+        assert!(check_polygon_is_convex(&transformed.vertices), "{transformed:?}");
+        assert!(transformed.signed_area() > 0.0);
+        assert!((transformed.area() - polygon.area() * affine.determinant().abs()).abs() < 1e-9);
+        let back = transformed.transform(&affine.inverse().unwrap());
+        assert!(back.vertices.iter().zip(&polygon.vertices).all(|(p, q)| p.distance(*q) < 1e-9));
+    }
+    assert!(mirrored > 20 && mirrored < 80);
+}
+
+#[test]
+fn test_minkowski_sum_of_square_and_triangle() {
+    let square = unit_square_at(0.0, 0.0);
+    let triangle = ConvexPolygon::new(&[Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 0.0 }, Point { x: 0.0, y: 1.0 }]);
+    let sum = square.minkowski_sum(&triangle);
+    assert_eq!(sum.vertices, vec![
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 2.0, y: 0.0 },
+        Point { x: 2.0, y: 1.0 },
+        Point { x: 1.0, y: 2.0 },
+        Point { x: 0.0, y: 2.0 },
+    ]);
+    assert_eq!(sum.area(), 3.5);
+
+    let difference = square.minkowski_difference(&unit_square_at(3.0, 0.5));
+    assert_eq!(difference.vertices, vec![
+        Point { x: -4.0, y: -1.5 },
+        Point { x: -2.0, y: -1.5 },
+        Point { x: -2.0, y: 0.5 },
+        Point { x: -4.0, y: 0.5 },
+    ]);
+    assert!(!difference.is_point_inside(Point { x: 0.0, y: 0.0 }));
+}
+
+#[test]
+fn test_minkowski_sum_matches_hull_of_sums() {
+    let mut rng = Lcg(22);
+    for _ in 0..200 {
+        let one = circle_polygon(rng.next_f64() * 4.0, rng.next_f64() * 4.0, 0.5 + rng.next_f64() * 2.0, 3 + rng.next_below(12), rng.next_f64());
+        let two = if rng.next_below(4) == 0 {
+            grid_polygon(&mut rng)
+        } else {
+            circle_polygon(rng.next_f64() * 4.0, rng.next_f64() * 4.0, 0.5 + rng.next_f64() * 2.0, 3 + rng.next_below(12), rng.next_f64())
+        };
+        let sum = one.minkowski_sum(&two);
+        let sums: Vec<_> = one.vertices.iter().flat_map(|p| two.vertices.iter().map(move |q| generic::Point::new(p.x + q.x, p.y + q.y))).collect();
+        let hull = generic::ConvexPolygon::hull(&sums);
+        assert!(sum.vertices.len() >= hull.vertices.len() && sum.vertices.len() <= one.vertices.len() + two.vertices.len());
+        assert!(check_polygon_is_convex(&sum.vertices), "{one:?} {two:?}");
+        assert!((sum.area() - hull.area()).abs() < 1e-9, "{one:?} {two:?}");
+        assert!(hull.vertices.iter().all(|v| sum.vertices.contains(v)), "{one:?} {two:?}");
+
+        let difference = one.minkowski_difference(&two);
+        assert_eq!(difference.is_point_inside_with_tolerance(generic::Point::new(0.0, 0.0), &generic::Tolerance::exact()), one.overlaps(&two), "{one:?} {two:?}");
+        if !one.overlaps(&two) {
+            let distance = difference.vertices.iter().enumerate()
+                .map(|(i, p)| generic::Segment::new(*p, difference.vertices[(i + 1) % difference.vertices.len()]).distance_to_point(generic::Point::new(0.0, 0.0)))
+                .fold(f64::MAX, f64::min);
+            assert!((distance - gjk_distance(&one, &two)).abs() < 1e-9);
+        }
+    }
+}
+
+#[test]
+fn test_offset_convex_polygon() {
+    let square = generic::ConvexPolygon::<f64>::new(&[
+        generic::Point::new(0.0, 0.0),
+        generic::Point::new(4.0, 0.0),
+        generic::Point::new(4.0, 4.0),
+        generic::Point::new(0.0, 4.0),
+    ]);
+    let mitered = square.offset(1.0, JoinStyle::Miter).unwrap();
+    assert_eq!(mitered.vertices, vec![
+        generic::Point::new(-1.0, -1.0),
+        generic::Point::new(5.0, -1.0),
+        generic::Point::new(5.0, 5.0),
+        generic::Point::new(-1.0, 5.0),
+    ]);
+    let bevelled = square.offset(1.0, JoinStyle::Bevel).unwrap();
+    assert_eq!(bevelled.vertices.len(), 8);
+    assert!((bevelled.area() - 34.0).abs() < 1e-12);
+    let rounded = square.offset(1.0, JoinStyle::Round { segments: 256 }).unwrap();
+    assert_eq!(rounded.vertices.len(), 4 * 65);
+    assert!(check_polygon_is_convex(&rounded.vertices));
+    assert!((rounded.area() - (16.0 + 16.0 + std::f64::consts::PI)).abs() < 1e-3);
+    assert_eq!(square.offset(0.0, JoinStyle::Round { segments: 16 }).unwrap(), square);
+
+    let shrunk = square.offset(-1.0, JoinStyle::Round { segments: 16 }).unwrap();
+    assert!((shrunk.area() - 4.0).abs() < 1e-12);
+    assert!(shrunk.vertices.contains(&generic::Point::new(1.0, 1.0)) && shrunk.vertices.contains(&generic::Point::new(3.0, 3.0)));
+    assert_eq!(square.offset(-2.0, JoinStyle::Miter), None);
+    assert_eq!(square.offset(-3.0, JoinStyle::Miter), None);
+}
+
+#[test]
+fn test_offset_simple_polygon() {
+    let polygon = Polygon::new(&l_shape());
+    let grown = polygon.offset(0.5, JoinStyle::Miter);
+    assert_eq!(grown.polygons.len(), 1);
+    assert_eq!(grown.polygons[0].exterior.vertices, vec![
+        Point { x: -0.5, y: -0.5 },
+        Point { x: 4.5, y: -0.5 },
+        Point { x: 4.5, y: 2.5 },
+        Point { x: 2.5, y: 2.5 },
+        Point { x: 2.5, y: 4.5 },
+        Point { x: -0.5, y: 4.5 },
+    ]);
+    let shrunk = polygon.offset(-0.5, JoinStyle::Miter);
+    assert_eq!(shrunk.area(), 5.0);
+    let rounded = polygon.offset(-0.5, JoinStyle::Round { segments: 1024 });
+    // the inner corner is rounded off, which keeps a little more than the sharp mitre
+    let expected = 5.0 + 0.25 - std::f32::consts::PI * 0.25 / 4.0;
+    assert!((rounded.area() - expected).abs() < 1e-3, "{}", rounded.area());
+    assert!(polygon.offset(-1.0, JoinStyle::Miter).is_empty());
+    // a bevel cuts less off the inner corner, leaving the triangle where the arms met
+    assert_eq!(polygon.offset(-1.0, JoinStyle::Bevel).area(), 0.5);
+    assert_eq!(polygon.offset(0.0, JoinStyle::Bevel).area(), 12.0);
+
+    // growing closes the mouth of a C into a hole, and shrinking cuts it into two
+    let c_shape = Polygon::new(&[
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 6.0, y: 0.0 },
+        Point { x: 6.0, y: 6.0 },
+        Point { x: 3.5, y: 6.0 },
+        Point { x: 3.5, y: 5.0 },
+        Point { x: 5.0, y: 5.0 },
+        Point { x: 5.0, y: 1.0 },
+        Point { x: 1.0, y: 1.0 },
+        Point { x: 1.0, y: 5.0 },
+        Point { x: 2.5, y: 5.0 },
+        Point { x: 2.5, y: 6.0 },
+        Point { x: 0.0, y: 6.0 },
+    ]);
+    let closed = c_shape.offset(0.75, JoinStyle::Miter);
+    assert_eq!(closed.polygons.len(), 1);
+    assert_eq!(closed.polygons[0].interiors.len(), 1);
+    assert_eq!(closed.polygons[0].interiors[0].area(), 2.5 * 2.5);
+    let thin = Polygon::new(&[
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 2.0, y: 0.0 },
+        Point { x: 2.0, y: 0.5 },
+        Point { x: 4.0, y: 0.5 },
+        Point { x: 4.0, y: 0.0 },
+        Point { x: 6.0, y: 0.0 },
+        Point { x: 6.0, y: 2.0 },
+        Point { x: 0.0, y: 2.0 },
+    ]);
+    let split = thin.offset(-0.8, JoinStyle::Miter);
+    assert_eq!(split.polygons.len(), 2);
+}
+
+#[test]
+fn test_offset_simple_polygon_matches_convex() {
+    let mut rng = Lcg(23);
+    for _ in 0..40 {
+        let convex = circle_polygon(rng.next_f64() * 4.0, rng.next_f64() * 4.0, 1.0 + rng.next_f64() * 2.0, 3 + rng.next_below(10), rng.next_f64());
+        let polygon = generic::Polygon::new(&convex.vertices);
+        let distance = rng.next_f64() * 2.0 - 1.0;
+        for style in [JoinStyle::Miter, JoinStyle::Bevel, JoinStyle::Round { segments: 32 }] {
+            let expected = convex.offset(distance, style).map_or(0.0, |p| p.area());
+            let offset = polygon.offset(distance, style);
+            assert!((offset.area() - expected).abs() < 1e-9, "{convex:?} {distance} {style:?}: {} {expected}", offset.area());
+            assert!(offset.polygons.len() <= 1);
+        }
+    }
+
+    for _ in 0..40 {
+        let Some(polygon) = star_polygon(&mut rng, false) else { continue };
+        let distance = 0.05 + rng.next_f64() * 0.5;
+        let grown = polygon.offset(distance, JoinStyle::Round { segments: 32 });
+        let shrunk = polygon.offset(-distance, JoinStyle::Round { segments: 32 });
+        assert!(grown.area() > polygon.area() && shrunk.area() < polygon.area(), "{polygon:?} {distance}");
+        assert!(polygon.vertices.iter().all(|v| grown.is_point_inside(*v)));
+        for piece in &shrunk.polygons {
+            assert!(piece.exterior.vertices.iter().all(|v| polygon.is_point_inside(*v)), "{polygon:?} {distance}");
+        }
+        // new vertices are about `distance` from the boundary; the arcs are cut short by their chords
+        let n = polygon.vertices.len();
+        let from_boundary = |p: generic::Point<f64>| (0..n)
+            .map(|i| generic::Segment::new(polygon.vertices[i], polygon.vertices[(i + 1) % n]).distance_to_point(p))
+            .fold(f64::MAX, f64::min);
+        assert!(grown.polygons.iter().flat_map(|p| &p.exterior.vertices).all(|v| from_boundary(*v) <= distance + 1e-9));
+        assert!(shrunk.polygons.iter().flat_map(|p| &p.exterior.vertices).all(|v| from_boundary(*v) >= distance * 0.99));
+    }
+}
+
+fn triangle_area<T: polyrust::Scalar>(vertices: &[generic::Point<T>], triangle: [usize; 3]) -> f64 {
+    orient2d(vertices[triangle[0]], vertices[triangle[1]], vertices[triangle[2]]) / 2.0
+}
+
+#[test]
+fn test_triangulate_convex_polygon_as_fan() {
+    let square = unit_square_at(2.0, 3.0);
+    assert_eq!(square.triangulate(), vec![[0, 1, 2], [0, 2, 3]]);
+    let mut rng = Lcg(24);
+    for _ in 0..20 {
+        let polygon = circle_polygon(rng.next_f64() * 4.0, rng.next_f64() * 4.0, 0.5 + rng.next_f64() * 2.0, 3 + rng.next_below(20), rng.next_f64());
+        let triangles = polygon.triangulate();
+        assert_eq!(triangles.len(), polygon.vertices.len() - 2);
+        assert!(triangles.iter().all(|t| triangle_area(&polygon.vertices, *t) > 0.0));
+        let total: f64 = triangles.iter().map(|t| triangle_area(&polygon.vertices, *t)).sum();
+        assert!((total - polygon.area()).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_triangulate_simple_polygon() {
+    let polygon = Polygon::new(&l_shape());
+    let triangles = polygon.triangulate();
+    assert_eq!(triangles.len(), 4);
+    assert!(triangles.iter().all(|t| triangle_area(&polygon.vertices, *t) > 0.0));
+    assert_eq!(triangles.iter().map(|t| triangle_area(&polygon.vertices, *t)).sum::<f64>(), 12.0);
+
+    // clockwise input still gives counter-clockwise triangles
+    let mut reversed = l_shape();
+    reversed.reverse();
+    let triangles = Polygon::new(&reversed).triangulate();
+    assert!(triangles.iter().all(|t| triangle_area(&reversed, *t) > 0.0));
+    assert_eq!(triangles.iter().map(|t| triangle_area(&reversed, *t)).sum::<f64>(), 12.0);
+
+    // a vertex between collinear neighbours is not an ear, but still ends up in a triangle
+    let with_midpoint = Polygon::new(&[
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 1.0, y: 0.0 },
+        Point { x: 2.0, y: 0.0 },
+        Point { x: 2.0, y: 2.0 },
+        Point { x: 0.0, y: 2.0 },
+    ]);
+    let triangles = with_midpoint.triangulate();
+    assert_eq!(triangles.len(), 3);
+    assert!(triangles.iter().all(|t| triangle_area(&with_midpoint.vertices, *t) > 0.0));
+    assert_eq!(triangles.iter().map(|t| triangle_area(&with_midpoint.vertices, *t)).sum::<f64>(), 4.0);
+}
+
+#[test]
+fn test_triangulate_random_polygons() {
+    let mut rng = Lcg(2024);
+    let mut tested = 0;
+    for round in 0..200 {
+        let Some(polygon) = star_polygon(&mut rng, round % 2 == 0) else { continue };
+        tested += 1;
+        let triangles = polygon.triangulate();
+        assert!(triangles.len() <= polygon.vertices.len() - 2);
+        assert!(triangles.iter().all(|t| triangle_area(&polygon.vertices, *t) > 0.0));
+        let total: f64 = triangles.iter().map(|t| triangle_area(&polygon.vertices, *t)).sum();
+        assert!((total - polygon.area()).abs() < 1e-9, "{total} {}", polygon.area());
+        // and each triangle lies inside the polygon
+        for t in &triangles {
+            let (a, b, c) = (polygon.vertices[t[0]], polygon.vertices[t[1]], polygon.vertices[t[2]]);
+            let centroid = generic::Point::new((a.x + b.x + c.x) / 3.0, (a.y + b.y + c.y) / 3.0);
+            assert!(polygon.is_point_inside(centroid));
+        }
+    }
+    assert!(tested > 100);
+}
+
+#[test]
+fn test_incircle() {
+    let (a, b, c) = (Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 0.0 }, Point { x: 0.0, y: 1.0 });
+    assert!(incircle(a, b, c, Point { x: 0.5, y: 0.5 }) > 0.0);
+    assert!(incircle(a, b, c, Point { x: 2.0, y: 2.0 }) < 0.0);
+    assert_eq!(incircle(a, b, c, Point { x: 1.0, y: 1.0 }), 0.0);
+    // clockwise order flips the sign
+    assert!(incircle(a, c, b, Point { x: 0.5, y: 0.5 }) < 0.0);
+
+    // far from the origin the fast estimate cannot decide, but the sign stays exact
+    let offset = 2f64.powi(40);
+    let at = |x: f64, y: f64| generic::Point::new(offset + x, offset + y);
+    assert_eq!(incircle(at(0.0, 0.0), at(1.0, 0.0), at(0.0, 1.0), at(1.0, 1.0)), 0.0);
+    let nudge = 2f64.powi(-12);
+    assert!(incircle(at(0.0, 0.0), at(1.0, 0.0), at(0.0, 1.0), at(1.0, 1.0 - nudge)) > 0.0);
+    assert!(incircle(at(0.0, 0.0), at(1.0, 0.0), at(0.0, 1.0), at(1.0, 1.0 + nudge)) < 0.0);
+}
+
+fn assert_mesh_consistent<T: polyrust::Scalar>(mesh: &generic::Mesh<T>) {
+    for (edge, half_edge) in mesh.half_edges.iter().enumerate() {
+        if let Some(twin) = half_edge.twin {
+            assert_eq!(mesh.half_edges[twin].twin, Some(edge));
+            assert_eq!(mesh.half_edges[twin].origin, mesh.destination(edge));
+            assert_eq!(mesh.half_edges[twin].constrained, half_edge.constrained);
+        }
+        assert_eq!(mesh.prev(mesh.next(edge)), edge);
+    }
+    for t in mesh.triangles() {
+        assert!(orient2d(mesh.vertices[t[0]], mesh.vertices[t[1]], mesh.vertices[t[2]]) > 0.0);
+    }
+}
+
+/// Whether no edge of the mesh has the far vertex of its neighbour strictly inside its
+/// circumcircle, except across constraints.
+fn is_locally_delaunay<T: polyrust::Scalar>(mesh: &generic::Mesh<T>) -> bool {
+    mesh.half_edges.iter().enumerate().all(|(edge, half_edge)| match half_edge.twin {
+        Some(twin) if !half_edge.constrained => {
+            let (a, b) = (mesh.vertices[half_edge.origin], mesh.vertices[mesh.destination(edge)]);
+            let near = mesh.vertices[mesh.half_edges[mesh.prev(edge)].origin];
+            let far = mesh.vertices[mesh.half_edges[mesh.prev(twin)].origin];
+            incircle(a, b, near, far) <= 0.0
+        }
+        _ => true,
+    })
+}
+
+fn mesh_area(mesh: &generic::Mesh<f64>) -> f64 {
+    mesh.triangles().iter().map(|t| orient2d(mesh.vertices[t[0]], mesh.vertices[t[1]], mesh.vertices[t[2]]) / 2.0).sum()
+}
+
+fn find_edge<T: polyrust::Scalar>(mesh: &generic::Mesh<T>, a: usize, b: usize) -> Option<usize> {
+    (0..mesh.half_edges.len()).find(|&e| {
+        let (from, to) = (mesh.half_edges[e].origin, mesh.destination(e));
+        (from, to) == (a, b) || (from, to) == (b, a)
+    })
+}
+
+fn has_edge<T: polyrust::Scalar>(mesh: &generic::Mesh<T>, a: usize, b: usize) -> bool {
+    find_edge(mesh, a, b).is_some()
+}
+
+fn has_constrained_edge<T: polyrust::Scalar>(mesh: &generic::Mesh<T>, a: usize, b: usize) -> bool {
+    find_edge(mesh, a, b).is_some_and(|e| mesh.half_edges[e].constrained)
+}
+
+#[test]
+fn test_delaunay_of_grid() {
+    // every cell of a grid has its corners on one circle, so only the counts are fixed
+    let points: Vec<Point> = (0..25).map(|i| Point { x: (i % 5) as f32, y: (i / 5) as f32 }).collect();
+    let mesh = Mesh::delaunay(&points);
+    assert_mesh_consistent(&mesh);
+    assert_eq!(mesh.triangle_count(), 32);
+    assert!(is_locally_delaunay(&mesh));
+    let area: f64 = mesh.triangles().iter().map(|t| orient2d(points[t[0]], points[t[1]], points[t[2]]) / 2.0).sum();
+    assert_eq!(area, 16.0);
+    assert_eq!(mesh.half_edges.iter().filter(|e| e.twin.is_none()).count(), 16);
+}
+
+#[test]
+fn test_delaunay_of_random_points() {
+    let mut rng = Lcg(25);
+    for round in 0..20 {
+        let n = 3 + rng.next_below(150);
+        let points: Vec<generic::Point<f64>> = (0..n)
+            .map(|_| {
+                let (x, y) = (rng.next_f64() * 10.0, rng.next_f64() * 10.0);
+                // every other round snaps to a coarse grid, for plenty of collinear and cocircular points
+                if round % 2 == 0 { generic::Point::new(x.round(), y.round()) } else { generic::Point::new(x, y) }
+            })
+            .collect();
+        let Ok(mesh) = generic::Mesh::try_delaunay(&points) else { continue };
+        assert_mesh_consistent(&mesh);
+        let hull = generic::ConvexPolygon::try_hull_with_collinear(&points, true).unwrap();
+        assert!((mesh_area(&mesh) - hull.area()).abs() < 1e-9);
+        // no point strictly inside any circumcircle
+        for t in mesh.triangles() {
+            let (a, b, c) = (points[t[0]], points[t[1]], points[t[2]]);
+            assert!(points.iter().all(|p| incircle(a, b, c, *p) <= 0.0));
+        }
+        // 2n - 2 - h triangles for n distinct points with h of them on the hull
+        let mut distinct = points.clone();
+        distinct.sort_by(|a, b| (a.x, a.y).partial_cmp(&(b.x, b.y)).unwrap());
+        distinct.dedup();
+        assert_eq!(mesh.triangle_count(), 2 * distinct.len() - 2 - hull.vertices.len());
+    }
+}
+
+#[test]
+fn test_delaunay_errors_and_repeated_points() {
+    assert_eq!(Mesh::try_delaunay(&[Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 0.0 }]), Err(PolyError::TooFewVertices { count: 2 }));
+    let collinear: Vec<Point> = (0..5).map(|i| Point { x: i as f32, y: 2.0 * i as f32 }).collect();
+    assert_eq!(Mesh::try_delaunay(&collinear), Err(PolyError::Degenerate));
+    let with_nan = [Point { x: 0.0, y: 0.0 }, Point { x: f32::NAN, y: 0.0 }, Point { x: 0.0, y: 1.0 }];
+    assert_eq!(Mesh::try_delaunay(&with_nan), Err(PolyError::NonFinite { index: 1 }));
+
+    // a long collinear run before the first point off the line, and a repeated point
+    let mut points = collinear.clone();
+    points.push(Point { x: 3.0, y: 0.0 });
+    points.push(Point { x: 1.0, y: 2.0 });
+    let mesh = Mesh::delaunay(&points);
+    assert_mesh_consistent(&mesh);
+    assert!(is_locally_delaunay(&mesh));
+    assert_eq!(mesh.triangle_count(), 4);
+    assert!(mesh.half_edges.iter().all(|e| e.origin != 6));
+}
+
+#[test]
+fn test_delaunay_with_constraints() {
+    // the short diagonal is Delaunay, but the long one is forced
+    let points = [Point { x: 0.0, y: 0.0 }, Point { x: 4.0, y: 0.0 }, Point { x: 2.0, y: -1.0 }, Point { x: 2.0, y: 1.0 }];
+    let free = Mesh::delaunay(&points);
+    assert!(has_edge(&free, 2, 3) && !has_edge(&free, 0, 1));
+    let forced = Mesh::try_delaunay_with_edges(&points, &[(0, 1)]).unwrap();
+    assert_mesh_consistent(&forced);
+    assert!(has_constrained_edge(&forced, 0, 1) && !has_edge(&forced, 2, 3));
+    let mut unmarked = forced.clone();
+    unmarked.half_edges.iter_mut().for_each(|e| e.constrained = false);
+    assert!(is_locally_delaunay(&forced) && !is_locally_delaunay(&unmarked));
+
+    // a constraint through another point is split there
+    let points = [Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 0.0 }, Point { x: 2.0, y: 0.0 }, Point { x: 1.0, y: 1.0 }, Point { x: 1.0, y: -1.0 }];
+    let mesh = Mesh::try_delaunay_with_edges(&points, &[(0, 2)]).unwrap();
+    assert!(has_constrained_edge(&mesh, 0, 1) && has_constrained_edge(&mesh, 1, 2));
+
+    let square = [Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 0.0 }, Point { x: 1.0, y: 1.0 }, Point { x: 0.0, y: 1.0 }];
+    assert_eq!(Mesh::try_delaunay_with_edges(&square, &[(0, 2), (1, 3)]), Err(PolyError::CrossingConstraints { first: 0, second: 1 }));
+    assert_eq!(Mesh::try_delaunay_with_edges(&square, &[(0, 2), (1, 4)]), Err(PolyError::InvalidConstraint { index: 1 }));
+}
+
+#[test]
+fn test_constrained_delaunay_of_polygons() {
+    let l = PolygonWithHoles::from(Polygon::new(&l_shape()));
+    let mesh = Mesh::constrained_delaunay(&l);
+    assert_mesh_consistent(&mesh);
+    assert_eq!(mesh.triangle_count(), 4);
+    assert!((0..6).all(|i| has_constrained_edge(&mesh, i, (i + 1) % 6)));
+    // nothing fills the bay between the arms
+    assert!(!has_edge(&mesh, 2, 4) && !has_edge(&mesh, 1, 4));
+
+    let holed = square_with_hole();
+    let mesh = Mesh::constrained_delaunay(&holed);
+    assert_mesh_consistent(&mesh);
+    assert!(is_locally_delaunay(&mesh));
+    let area: f32 = mesh.triangles().iter().map(|t| orient2d(mesh.vertices[t[0]], mesh.vertices[t[1]], mesh.vertices[t[2]]) as f32 / 2.0).sum();
+    assert_eq!(area, holed.area());
+    assert_eq!(mesh.half_edges.iter().filter(|e| e.constrained && e.twin.is_none()).count(), 8);
+}
+
+#[test]
+fn test_constrained_delaunay_of_random_polygons() {
+    let mut rng = Lcg(250);
+    let mut tested = 0;
+    for round in 0..200 {
+        let Some(polygon) = star_polygon(&mut rng, round % 2 == 0) else { continue };
+        tested += 1;
+        let n = polygon.vertices.len();
+        let mesh = generic::Mesh::constrained_delaunay(&polygon.clone().into());
+        assert_mesh_consistent(&mesh);
+        assert!(is_locally_delaunay(&mesh));
+        assert!((mesh_area(&mesh) - polygon.area()).abs() < 1e-9);
+        // the boundary is exactly the polygon's edges
+        assert!((0..n).all(|i| has_constrained_edge(&mesh, i, (i + 1) % n)));
+        assert!(mesh.half_edges.iter().all(|e| e.twin.is_none() == e.constrained));
+    }
+    assert!(tested > 100);
+}